@@ -83,6 +83,12 @@ const POOL_NO_TAGS: i32 = POOL_INVALID_TX + 9;
 const POOL_INVALID_BLOCK_ID: i32 = POOL_INVALID_TX + 10;
 /// The pool is not accepting future transactions.
 const POOL_FUTURE_TX: i32 = POOL_INVALID_TX + 11;
+/// The transaction exceeds the maximal size accepted by the pool.
+const POOL_TOO_LARGE: i32 = POOL_INVALID_TX + 12;
+/// The transaction is not allowed by the local filter of the pool.
+const POOL_FILTERED: i32 = POOL_INVALID_TX + 13;
+/// The validation of the transaction did not finish in time.
+const POOL_VALIDATION_TIMEOUT: i32 = POOL_INVALID_TX + 14;
 /// Other error.
 const OTHER_ERR: i32 = BASE_ERROR + 40;
 
@@ -175,6 +181,23 @@ impl From<Error> for ErrorObjectOwned {
 					None::<()>,
 				)
 			},
+			Error::Pool(PoolError::TooLarge { size, limit }) => {
+				ErrorObject::owned(
+					POOL_TOO_LARGE,
+					"Transaction is too large",
+					Some(format!("Transaction size {} exceeds the limit of {} bytes", size, limit)),
+				)
+			},
+			Error::Pool(PoolError::Filtered) => ErrorObject::owned(
+				POOL_FILTERED,
+				"Transaction is not allowed by the local filter of the pool",
+				None::<()>,
+			),
+			Error::Pool(PoolError::ValidationTimeout) => ErrorObject::owned(
+				POOL_VALIDATION_TIMEOUT,
+				"Transaction validation did not finish in time",
+				None::<()>,
+			),
 			Error::UnsafeRpcCalled(e) => e.into(),
 			other => ErrorObject::owned(
 				OTHER_ERR,
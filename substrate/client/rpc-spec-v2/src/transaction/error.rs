@@ -96,6 +96,17 @@ impl<Hash> From<Error> for TransactionEvent<Hash> {
 				TransactionEvent::Invalid(TransactionError {
 					error: "The pool is not accepting future transactions".into(),
 				}),
+			Error::Pool(PoolError::TooLarge { size, limit }) =>
+				TransactionEvent::Invalid(TransactionError {
+					error: format!("The transaction is too large ({} > {} bytes)", size, limit),
+				}),
+			Error::Pool(PoolError::Filtered) => TransactionEvent::Invalid(TransactionError {
+				error: "The transaction is not allowed by the local filter of the pool".into(),
+			}),
+			Error::Pool(PoolError::ValidationTimeout) =>
+				TransactionEvent::Invalid(TransactionError {
+					error: "The transaction validation did not finish in time".into(),
+				}),
 		}
 	}
 }
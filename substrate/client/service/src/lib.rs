@@ -616,4 +616,32 @@ mod tests {
 		assert_eq!(transactions.len(), 1);
 		assert!(TransferData::try_from(&*transactions[0].1).is_ok());
 	}
+
+	#[test]
+	fn should_not_propagate_transactions_marked_as_such_regardless_of_signer() {
+		// given
+		let (client, longest_chain) = TestClientBuilder::new().build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let pool = Arc::from(BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner,
+			client.clone(),
+		));
+		let source = sp_runtime::transaction_validity::TransactionSource::External;
+		let best = block_on(longest_chain.best_chain()).unwrap();
+		for signer in [Sr25519Keyring::Alice, Sr25519Keyring::Bob, Sr25519Keyring::Charlie] {
+			let xt = ExtrinsicBuilder::new_call_do_not_propagate_signed(signer.pair()).build();
+			block_on(pool.submit_one(best.hash(), source, xt)).unwrap();
+		}
+		assert_eq!(pool.status().ready, 3);
+
+		// when
+		let transactions = transactions_to_propagate(&*pool);
+
+		// then
+		assert!(transactions.is_empty());
+	}
 }
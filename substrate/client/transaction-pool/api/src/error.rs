@@ -69,6 +69,20 @@ pub enum Error {
 
 	#[error("The pool is not accepting future transactions")]
 	RejectedFutureTransaction,
+
+	#[error("Transaction is too large ({size} > {limit} bytes)")]
+	TooLarge {
+		/// Encoded length of the transaction.
+		size: usize,
+		/// The maximal encoded length of the transaction accepted by the pool.
+		limit: usize,
+	},
+
+	#[error("Transaction is not allowed by the local filter of the pool")]
+	Filtered,
+
+	#[error("Transaction validation did not finish in time")]
+	ValidationTimeout,
 }
 
 impl Error {
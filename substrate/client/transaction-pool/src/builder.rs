@@ -98,6 +98,20 @@ impl TransactionPoolOptions {
 				},
 				reject_future_transactions: false,
 				ban_time: Duration::from_secs(30 * 60),
+				keep_unknown_local_transactions: false,
+				validate_at_best: false,
+				max_consecutive_invalid: None,
+				revalidation_resubmit_chunk_size: None,
+				slow_submit_many_threshold: None,
+				validation_cache_size: None,
+				validation_timeout: None,
+				unknown_quarantine_max_retries: None,
+				max_extrinsic_size: None,
+				max_concurrent_revalidations: 1,
+				soft_limit_ratio: None,
+				revalidation_finish_request_channel_capacity: 1,
+				revalidation_result_channel_capacity: 1,
+				revalidation_metrics_flush_interval: None,
 			},
 			txpool_type: TransactionPoolType::SingleState,
 		}
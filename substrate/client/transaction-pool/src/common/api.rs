@@ -210,6 +210,10 @@ where
 	) -> Result<TreeRoute<Self::Block>, Self::Error> {
 		sp_blockchain::tree_route::<Block, Client>(&*self.client, from, to).map_err(Into::into)
 	}
+
+	fn best_block_hash(&self) -> Result<Option<<Self::Block as BlockT>::Hash>, Self::Error> {
+		Ok(Some(self.client.info().best_hash))
+	}
 }
 
 /// Helper function to validate a transaction using a full chain API.
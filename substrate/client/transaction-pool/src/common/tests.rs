@@ -25,15 +25,16 @@ use sc_transaction_pool_api::error;
 use sp_blockchain::{HashAndNumber, TreeRoute};
 use sp_runtime::{
 	generic::BlockId,
-	traits::{Block as BlockT, Hash},
+	traits::{Block as BlockT, Hash, Header as HeaderT},
 	transaction_validity::{
-		InvalidTransaction, TransactionSource, TransactionValidity, ValidTransaction,
+		InvalidTransaction, TransactionSource, TransactionValidity, UnknownTransaction,
+		ValidTransaction,
 	},
 };
 use std::{collections::HashSet, sync::Arc};
 use substrate_test_runtime::{
 	substrate_test_pallet::pallet::Call as PalletCall, BalancesCall, Block, BlockNumber, Extrinsic,
-	ExtrinsicBuilder, Hashing, RuntimeCall, Transfer, TransferData, H256,
+	ExtrinsicBuilder, Hashing, Header, RuntimeCall, Transfer, TransferData, H256,
 };
 
 type Pool<Api> = crate::graph::Pool<Api, ()>;
@@ -45,9 +46,15 @@ pub(crate) const INVALID_NONCE: u64 = 254;
 pub(crate) struct TestApi {
 	pub delay: Arc<Mutex<Option<std::sync::mpsc::Receiver<()>>>>,
 	pub invalidate: Arc<Mutex<HashSet<H256>>>,
+	pub unknown: Arc<Mutex<HashSet<H256>>>,
 	pub clear_requirements: Arc<Mutex<HashSet<H256>>>,
 	pub add_requirements: Arc<Mutex<HashSet<H256>>>,
+	/// Transactions which validation fails with an error (i.e. no validity is returned).
+	pub fail_validation: Arc<Mutex<HashSet<H256>>>,
+	/// Transactions which validation never finishes.
+	pub stall_validation: Arc<Mutex<HashSet<H256>>>,
 	pub validation_requests: Arc<Mutex<Vec<Extrinsic>>>,
+	pub best_block: Arc<Mutex<Option<H256>>>,
 }
 
 impl TestApi {
@@ -69,7 +76,10 @@ impl TestApi {
 impl ChainApi for TestApi {
 	type Block = Block;
 	type Error = error::Error;
-	type ValidationFuture = futures::future::Ready<error::Result<TransactionValidity>>;
+	type ValidationFuture = futures::future::Either<
+		futures::future::Ready<error::Result<TransactionValidity>>,
+		futures::future::Pending<error::Result<TransactionValidity>>,
+	>;
 	type BodyFuture = futures::future::Ready<error::Result<Option<Vec<Extrinsic>>>>;
 
 	/// Verify extrinsic at given block.
@@ -84,6 +94,15 @@ impl ChainApi for TestApi {
 		let hash = self.hash_and_length(&uxt).0;
 		let block_number = self.block_id_to_number(&BlockId::Hash(at)).unwrap().unwrap();
 
+		if self.fail_validation.lock().contains(&hash) {
+			return futures::future::Either::Left(futures::future::ready(Err(
+				error::Error::InvalidBlockId(format!("{at:?}")),
+			)))
+		}
+		if self.stall_validation.lock().contains(&hash) {
+			return futures::future::Either::Right(futures::future::pending())
+		}
+
 		let res = match uxt {
 			Extrinsic {
 				function: RuntimeCall::Balances(BalancesCall::transfer_allow_death { .. }),
@@ -102,6 +121,8 @@ impl ChainApi for TestApi {
 
 				if self.invalidate.lock().contains(&hash) {
 					InvalidTransaction::Custom(0).into()
+				} else if self.unknown.lock().contains(&hash) {
+					UnknownTransaction::CannotLookup.into()
 				} else if nonce < block_number {
 					InvalidTransaction::Stale.into()
 				} else {
@@ -155,16 +176,16 @@ impl ChainApi for TestApi {
 			_ => unimplemented!(),
 		};
 
-		futures::future::ready(Ok(res))
+		futures::future::Either::Left(futures::future::ready(Ok(res)))
 	}
 
 	fn validate_transaction_blocking(
 		&self,
-		_at: <Self::Block as BlockT>::Hash,
-		_source: TransactionSource,
-		_uxt: Arc<<Self::Block as BlockT>::Extrinsic>,
+		at: <Self::Block as BlockT>::Hash,
+		source: TransactionSource,
+		uxt: Arc<<Self::Block as BlockT>::Extrinsic>,
 	) -> error::Result<TransactionValidity> {
-		unimplemented!();
+		futures::executor::block_on(self.validate_transaction(at, source, uxt))
 	}
 
 	/// Returns a block number given the block id.
@@ -191,6 +212,11 @@ impl ChainApi for TestApi {
 		})
 	}
 
+	/// Returns the best block hash, if it was set.
+	fn best_block_hash(&self) -> Result<Option<<Self::Block as BlockT>::Hash>, Self::Error> {
+		Ok(*self.best_block.lock())
+	}
+
 	/// Hash the extrinsic.
 	fn hash_and_length(&self, uxt: &RawExtrinsicFor<Self>) -> (BlockHash<Self>, usize) {
 		let encoded = uxt.encode();
@@ -202,11 +228,21 @@ impl ChainApi for TestApi {
 		futures::future::ready(Ok(None))
 	}
 
+	/// Returns the header of the block given the block hash, its parent is the block with the
+	/// preceding number.
 	fn block_header(
 		&self,
-		_: <Self::Block as BlockT>::Hash,
+		hash: <Self::Block as BlockT>::Hash,
 	) -> Result<Option<<Self::Block as BlockT>::Header>, Self::Error> {
-		Ok(None)
+		Ok(self.block_id_to_number(&BlockId::Hash(hash))?.map(|number| {
+			Header::new(
+				number,
+				Default::default(),
+				Default::default(),
+				H256::from_low_u64_be(number.saturating_sub(1)),
+				Default::default(),
+			)
+		}))
 	}
 
 	fn tree_route(
@@ -24,7 +24,10 @@ use super::{
 	metrics::{EventsMetricsCollector, MetricsLink as PrometheusMetrics},
 	multi_view_listener::MultiViewListener,
 	tx_mem_pool::{InsertionInfo, TxMemPool, TXMEMPOOL_TRANSACTION_LIMIT_MULTIPLIER},
-	view::View,
+	view::{
+		ImportState, PreValidationFilter, ReplaceOutcome, RevalidationRun, StagedOutcome,
+		ValidationTracer, View, ViewPin, ViewStats,
+	},
 	view_store::ViewStore,
 };
 use crate::{
@@ -52,17 +55,20 @@ use futures::{
 use parking_lot::Mutex;
 use prometheus_endpoint::Registry as PrometheusRegistry;
 use sc_transaction_pool_api::{
-	error::Error as TxPoolApiError, ChainEvent, ImportNotificationStream,
-	MaintainedTransactionPool, PoolStatus, TransactionFor, TransactionPool, TransactionPriority,
-	TransactionSource, TransactionStatusStreamFor, TxHash, TxInvalidityReportMap,
+	error::{Error as TxPoolApiError, IntoPoolError},
+	ChainEvent, ImportNotificationStream, MaintainedTransactionPool, PoolStatus, TransactionFor,
+	TransactionPool, TransactionPriority, TransactionSource, TransactionStatusStreamFor, TxHash,
+	TxInvalidityReportMap,
 };
 use sp_blockchain::{HashAndNumber, TreeRoute};
 use sp_core::traits::SpawnEssentialNamed;
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block as BlockT, NumberFor},
-	transaction_validity::{TransactionTag as Tag, TransactionValidityError, ValidTransaction},
-	Saturating,
+	transaction_validity::{
+		TransactionTag as Tag, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	Saturating, Weight,
 };
 use std::{
 	collections::{BTreeMap, HashMap, HashSet},
@@ -183,6 +189,13 @@ where
 	/// Intended to be used in the finality stall cleanups and also as a cache for all in-block
 	/// transactions.
 	included_transactions: Mutex<BTreeMap<HashAndNumber<Block>, Vec<ExtrinsicHash<ChainApi>>>>,
+
+	/// Optional predicate checked against every submitted transaction before it is validated by
+	/// the runtime.
+	pre_validation_filter: Option<PreValidationFilter<ChainApi>>,
+
+	/// Optional hook observing the runtime validations performed by the views.
+	validation_tracer: Option<ValidationTracer<ChainApi>>,
 }
 
 impl<ChainApi, Block> ForkAwareTxPool<ChainApi, Block>
@@ -278,6 +291,8 @@ where
 				finality_timeout_threshold: finality_timeout_threshold
 					.unwrap_or(FINALITY_TIMEOUT_THRESHOLD),
 				included_transactions: Default::default(),
+				pre_validation_filter: None,
+				validation_tracer: None,
 			},
 			combined_tasks,
 		)
@@ -356,7 +371,10 @@ where
 		let listener = Arc::new(listener);
 
 		let (revalidation_queue, revalidation_task) =
-			revalidation_worker::RevalidationQueue::new_with_worker();
+			revalidation_worker::RevalidationQueue::new_with_worker(
+				options.max_concurrent_revalidations,
+				metrics.clone(),
+			);
 
 		let (import_notification_sink, import_notification_sink_task) =
 			MultiViewImportNotificationSink::new_with_worker();
@@ -411,9 +429,54 @@ where
 			is_validator,
 			finality_timeout_threshold: FINALITY_TIMEOUT_THRESHOLD,
 			included_transactions: Default::default(),
+			pre_validation_filter: None,
+			validation_tracer: None,
 		}
 	}
 
+	/// Sets the predicate checked against every transaction submitted to the views, before the
+	/// transaction is validated by the runtime.
+	///
+	/// The filter is used by the views created from scratch and inherited by the cloned views, so
+	/// it shall be set before the first view is created. Refer to [`PreValidationFilter`] for more
+	/// details.
+	pub fn with_pre_validation_filter(mut self, filter: PreValidationFilter<ChainApi>) -> Self {
+		self.pre_validation_filter = Some(filter);
+		self
+	}
+
+	/// Restricts the transactions accepted by the views to the ones allowed by `allowed`.
+	///
+	/// Intended for filtering the call types (e.g. refusing the spam of some call) on specialized
+	/// nodes. Disallowed transactions are rejected with [`TxPoolApiError::Filtered`] before the
+	/// runtime validation. The predicate is combined with the pre-validation filter set so far
+	/// (if any), so the same rules as for [`Self::with_pre_validation_filter`] apply.
+	pub fn with_call_filter(
+		mut self,
+		allowed: impl Fn(&ExtrinsicFor<ChainApi>) -> bool + Send + Sync + 'static,
+	) -> Self {
+		let filter = self.pre_validation_filter.take();
+		self.pre_validation_filter = Some(Arc::new(move |xt| {
+			filter.as_ref().map_or(Ok(()), |filter| filter(xt))?;
+			if allowed(xt) {
+				Ok(())
+			} else {
+				Err(TxPoolApiError::Filtered)
+			}
+		}));
+		self
+	}
+
+	/// Sets the hook observing the runtime validations performed by the views.
+	///
+	/// The hook is used by the views created from scratch and inherited by the cloned views, so
+	/// it shall be set before the first view is created. Refer to [`ValidationTracer`] for more
+	/// details.
+	pub fn with_validation_tracer(mut self, tracer: ValidationTracer<ChainApi>) -> Self {
+		self.validation_tracer = Some(tracer);
+		self
+	}
+
 	/// Get access to the underlying api
 	pub fn api(&self) -> &ChainApi {
 		&self.api
@@ -443,7 +506,7 @@ where
 			.active_views
 			.read()
 			.iter()
-			.map(|v| (v.1.at.number, v.1.status().ready, v.1.status().future))
+			.map(|v| (v.1.number(), v.1.status().ready, v.1.status().future))
 			.collect()
 	}
 
@@ -469,6 +532,769 @@ where
 		self.view_store.futures_at(at)
 	}
 
+	/// Pins the view at given block hash, deferring its removal on finalization.
+	///
+	/// The view is kept in the pool as long as the returned guard is alive. Refer to
+	/// [`View::pin`] for more details, including the risk of keeping the guard alive for too long.
+	///
+	/// Returns `None` if there is no view at given block.
+	pub fn pin_view_at(&self, at: Block::Hash) -> Option<ViewPin<ChainApi>> {
+		self.view_store.get_view_at(at, true).map(|(view, _)| view.pin())
+	}
+
+	/// Returns the statistics snapshot of the view at given block hash.
+	///
+	/// Intended for logging / tests / RPC.
+	pub fn view_stats_at(&self, at: Block::Hash) -> Option<ViewStats> {
+		self.view_store.get_view_at(at, true).map(|(v, _)| v.stats_snapshot())
+	}
+
+	/// For each hash, returns the transaction (either ready or future) if it is in the view at
+	/// given block hash.
+	///
+	/// Provides a read-only access to the transactions kept in the internal pool of the view.
+	/// Returns `None` if there is no view at given block.
+	pub fn transactions_by_hashes_at(
+		&self,
+		at: Block::Hash,
+		hashes: &[ExtrinsicHash<ChainApi>],
+	) -> Option<Vec<Option<Arc<Transaction<ExtrinsicHash<ChainApi>, ExtrinsicFor<ChainApi>>>>>> {
+		self.view_store
+			.get_view_at(at, true)
+			.map(|(view, _)| view.transactions_by_hashes(hashes))
+	}
+
+	/// Returns `true` if the transaction with given hash is imported into the view at given block
+	/// hash.
+	///
+	/// Returns `None` if there is no view at given block.
+	pub fn is_imported_at(
+		&self,
+		at: Block::Hash,
+		tx_hash: &ExtrinsicHash<ChainApi>,
+	) -> Option<bool> {
+		self.view_store.get_view_at(at, true).map(|(view, _)| view.is_imported(tx_hash))
+	}
+
+	/// Returns the state of the transaction with given hash in the view at given block hash.
+	///
+	/// Refer to [`View::import_state`] for more details. Returns `None` if there is no view at
+	/// given block.
+	pub fn import_state_at(
+		&self,
+		at: Block::Hash,
+		tx_hash: &ExtrinsicHash<ChainApi>,
+	) -> Option<ImportState> {
+		self.view_store
+			.get_view_at(at, true)
+			.map(|(view, _)| view.import_state(tx_hash))
+	}
+
+	/// Returns the validity of the transaction with given hash stored in the view at given block
+	/// hash.
+	///
+	/// Refer to [`View::get_validity`] for more details. Returns `None` if there is no view at
+	/// given block or the transaction is not in the view.
+	pub fn get_validity_at(
+		&self,
+		at: Block::Hash,
+		tx_hash: &ExtrinsicHash<ChainApi>,
+	) -> Option<ValidTransaction> {
+		self.view_store
+			.get_view_at(at, true)
+			.and_then(|(view, _)| view.get_validity(tx_hash))
+	}
+
+	/// Validates the transaction at the view at given block hash with the given context.
+	///
+	/// If `recheck` is `false`, the validity known to the view is returned without calling into
+	/// the runtime. Refer to [`View::validate_transaction_with_context`] for more details.
+	///
+	/// Returns `None` if there is no view at given block.
+	pub async fn validate_transaction_with_context_at(
+		&self,
+		at: Block::Hash,
+		source: TransactionSource,
+		xt: RawExtrinsicFor<ChainApi>,
+		recheck: bool,
+	) -> Option<Result<TransactionValidity, ChainApi::Error>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		Some(view.validate_transaction_with_context(source, Arc::from(xt), recheck).await)
+	}
+
+	/// Refreshes the timestamp of the transaction with given hash in the view at given block hash
+	/// to now, without revalidating it.
+	///
+	/// Returns `None` if there is no view at given block, otherwise `Some(false)` if the
+	/// transaction is not in the view.
+	pub fn touch_at(&self, at: Block::Hash, tx_hash: &ExtrinsicHash<ChainApi>) -> Option<bool> {
+		self.view_store.get_view_at(at, true).map(|(view, _)| view.touch(tx_hash))
+	}
+
+	/// Returns `true` if the background revalidation of the view at given block hash is currently
+	/// in progress.
+	///
+	/// Returns `None` if there is no view at given block.
+	pub fn revalidation_in_progress_at(&self, at: Block::Hash) -> Option<bool> {
+		self.view_store
+			.get_view_at(at, true)
+			.map(|(view, _)| view.revalidation_in_progress())
+	}
+
+	/// Returns the instant at which the most recent background revalidation of the view at given
+	/// block hash was completed.
+	///
+	/// Returns `None` if there is no view at given block or the view was never revalidated.
+	/// Intended for logging / tests / scheduling the revalidation of views.
+	pub fn last_revalidation_instant_at(&self, at: Block::Hash) -> Option<Instant> {
+		self.view_store
+			.get_view_at(at, true)
+			.and_then(|(view, _)| view.last_revalidation_instant())
+	}
+
+	/// Returns the hashes of ready transactions in the view at given block hash, in no particular
+	/// order.
+	///
+	/// Returns `None` if there is no view at given block.
+	pub fn ready_hashes_at(&self, at: Block::Hash) -> Option<Vec<ExtrinsicHash<ChainApi>>> {
+		self.view_store.get_view_at(at, true).map(|(v, _)| v.ready_hashes())
+	}
+
+	/// Returns a stream of hashes of the transactions removed by the revalidation of the view at
+	/// given block hash.
+	///
+	/// Refer to [`View::revalidation_removed_stream`] for more details. Returns `None` if there is
+	/// no view at given block.
+	pub fn revalidation_removed_stream_at(
+		&self,
+		at: Block::Hash,
+	) -> Option<impl Stream<Item = ExtrinsicHash<ChainApi>>> {
+		self.view_store
+			.get_view_at(at, true)
+			.map(|(v, _)| v.revalidation_removed_stream())
+	}
+
+	/// Revalidates at most `budget` ready transactions of the view at given block hash and
+	/// applies the results.
+	///
+	/// Refer to [`View::revalidate_with_budget`] for more details. Returns `None` if there is no
+	/// view at given block.
+	pub async fn revalidate_with_budget_at(
+		&self,
+		at: Block::Hash,
+		budget: usize,
+	) -> Option<RevalidationRun> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		Some(view.revalidate_with_budget(budget).await)
+	}
+
+	/// Returns the receiver observing the status of the view at given block hash.
+	///
+	/// Refer to [`View::subscribe_status`] for more details. Returns `None` if there is no view at
+	/// given block.
+	pub fn subscribe_status_at(
+		&self,
+		at: Block::Hash,
+	) -> Option<tokio::sync::watch::Receiver<PoolStatus>> {
+		self.view_store.get_view_at(at, true).map(|(v, _)| v.subscribe_status())
+	}
+
+	/// Returns the hashes of ready transactions submitted at or after `since` for given block
+	/// hash, ordered by the submission time.
+	///
+	/// Intended for polling the newly added transactions.
+	pub fn ready_since_at(
+		&self,
+		at: Block::Hash,
+		since: Instant,
+	) -> Option<Vec<ExtrinsicHash<ChainApi>>> {
+		self.view_store.get_view_at(at, true).map(|(v, _)| v.ready_since(since))
+	}
+
+	/// Returns at most `n` ready transactions of the view at given block hash, in the order they
+	/// would be included into the block.
+	///
+	/// Refer to [`View::ready_iter_limited`] for more details. Returns `None` if there is no view
+	/// at given block.
+	pub fn ready_iter_limited_at(
+		&self,
+		at: Block::Hash,
+		n: usize,
+	) -> Option<Vec<graph::TransactionFor<ChainApi>>> {
+		self.view_store.get_view_at(at, true).map(|(v, _)| v.ready_iter_limited(n))
+	}
+
+	/// Returns the bodies of the ready transactions of the view at given block hash, in the order
+	/// they would be included into the block.
+	///
+	/// Refer to [`View::pending_extrinsics`] for more details. Returns `None` if there is no view
+	/// at given block.
+	pub fn pending_extrinsics_at(&self, at: Block::Hash) -> Option<Vec<ExtrinsicFor<ChainApi>>> {
+		self.view_store.get_view_at(at, true).map(|(v, _)| v.pending_extrinsics())
+	}
+
+	/// Returns an iterator over the ready transactions of the view at given block hash together
+	/// with their sources.
+	///
+	/// Refer to [`View::ready_with_sources`] for more details. Returns `None` if there is no view
+	/// at given block.
+	pub fn ready_with_sources_at(
+		&self,
+		at: Block::Hash,
+	) -> Option<impl Iterator<Item = (graph::TransactionFor<ChainApi>, TimedTransactionSource)>> {
+		self.view_store.get_view_at(at, true).map(|(v, _)| v.ready_with_sources())
+	}
+
+	/// Returns the tags required by the future transactions of the view at given block hash which
+	/// are not provided by any ready transaction.
+	///
+	/// Intended for logging / tests / debugging the transactions which are not progressing.
+	pub fn missing_tags_at(&self, at: Block::Hash) -> Option<Vec<Tag>> {
+		self.view_store.get_view_at(at, true).map(|(view, _)| view.missing_tags())
+	}
+
+	/// Returns the tags provided by the ready transactions of the view at given block hash, mapped
+	/// to the providing transaction.
+	///
+	/// Refer to [`View::provides_index`] for more details. Returns `None` if there is no view at
+	/// given block.
+	pub fn provides_index_at(
+		&self,
+		at: Block::Hash,
+	) -> Option<HashMap<Tag, ExtrinsicHash<ChainApi>>> {
+		self.view_store.get_view_at(at, true).map(|(view, _)| view.provides_index())
+	}
+
+	/// Searches the view at given block hash for the transaction sent by `sender` with given
+	/// `nonce`.
+	///
+	/// The sender and nonce of the transaction are provided by the runtime-specific `extract`
+	/// closure. Refer to [`View::find_by_sender_nonce`] for more details.
+	pub fn find_by_sender_nonce_at<AccountId: PartialEq, Nonce: PartialEq>(
+		&self,
+		at: Block::Hash,
+		extract: impl Fn(&ExtrinsicFor<ChainApi>) -> Option<(AccountId, Nonce)>,
+		sender: &AccountId,
+		nonce: Nonce,
+	) -> Option<ExtrinsicHash<ChainApi>> {
+		self.view_store
+			.get_view_at(at, true)
+			.and_then(|(view, _)| view.find_by_sender_nonce(extract, sender, nonce))
+	}
+
+	/// Estimates how many ready transactions of the view at given block hash fit into the block
+	/// of given `max_weight`.
+	///
+	/// The weight of the transaction is provided by the runtime-specific `weight_of` closure.
+	/// Refer to [`View::estimate_block_fill`] for more details. Returns `None` if there is no view
+	/// at given block.
+	pub fn estimate_block_fill_at(
+		&self,
+		at: Block::Hash,
+		max_weight: Weight,
+		weight_of: impl Fn(&ExtrinsicFor<ChainApi>) -> Weight,
+	) -> Option<(usize, Weight)> {
+		self.view_store
+			.get_view_at(at, true)
+			.map(|(view, _)| view.estimate_block_fill(max_weight, weight_of))
+	}
+
+	/// Submits the given transactions to the view at given block hash, yielding the results as a
+	/// stream.
+	///
+	/// Every item of the stream contains the index of transaction in `xts` and the result of its
+	/// submission. Transactions are added to the internal mempool first, the ones rejected by the
+	/// mempool are yielded before the results of the view submission. Transactions rejected by
+	/// the view are removed from the mempool. Refer to [`View::submit_many_stream`] for more
+	/// details.
+	///
+	/// Intended for tests / tooling processing large batches of transactions.
+	pub fn submit_many_stream_at(
+		&self,
+		at: Block::Hash,
+		source: TransactionSource,
+		xts: Vec<RawExtrinsicFor<ChainApi>>,
+	) -> Option<impl Stream<Item = (usize, Result<ExtrinsicHash<ChainApi>, ChainApi::Error>)>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		let (mempool_results, to_be_submitted) = self.insert_into_mempool(source, xts);
+
+		let mut rejected = Vec::new();
+		let mut submitted = Vec::with_capacity(to_be_submitted.len());
+		for (index, result) in mempool_results.into_iter().enumerate() {
+			match result {
+				Ok(tx_hash) => submitted.push((index, tx_hash)),
+				Err(error) => rejected.push((index, Err(error))),
+			}
+		}
+
+		let mempool = self.mempool.clone();
+		let results = view.submit_many_stream(to_be_submitted).map(move |(index, result)| {
+			let (index, tx_hash) = submitted[index];
+			(index, mempool.handle_view_submission_result(tx_hash, result.map(Into::into)))
+		});
+		Some(futures::stream::iter(rejected).chain(results))
+	}
+
+	/// Synchronously submits the given local transaction to the view at given block hash,
+	/// returning its hash and priority.
+	///
+	/// The transaction is added to the internal mempool first, if it is rejected by the view it is
+	/// removed from the mempool again. Refer to [`View::submit_local_with_priority`] for more
+	/// details. Returns `None` if there is no view at given block.
+	pub fn submit_local_with_priority_at(
+		&self,
+		at: Block::Hash,
+		xt: RawExtrinsicFor<ChainApi>,
+	) -> Option<Result<(ExtrinsicHash<ChainApi>, TransactionPriority), ChainApi::Error>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		let (mut mempool_results, mut to_be_submitted) =
+			self.insert_into_mempool(TransactionSource::Local, vec![xt]);
+		let tx_hash = match mempool_results.remove(0) {
+			Ok(tx_hash) => tx_hash,
+			Err(error) => return Some(Err(error)),
+		};
+
+		let (_, xt) = to_be_submitted.remove(0);
+		let result = match view.submit_local_with_priority(xt) {
+			Ok((outcome, priority)) => {
+				self.mempool.update_transaction_priority(&outcome.into());
+				Ok((tx_hash, priority))
+			},
+			Err(error) => {
+				self.mempool.remove_transactions(&[tx_hash]);
+				Err(error)
+			},
+		};
+		Some(result)
+	}
+
+	/// Synchronously submits the given local transactions to the view at given block hash.
+	///
+	/// The transactions are added to the internal mempool first, the ones accepted by the mempool
+	/// are submitted to the view together. The transactions rejected by the view are removed from
+	/// the mempool again. Refer to [`View::submit_local_many`] for more details. Returns `None` if
+	/// there is no view at given block.
+	pub fn submit_local_many_at(
+		&self,
+		at: Block::Hash,
+		xts: Vec<RawExtrinsicFor<ChainApi>>,
+	) -> Option<Vec<Result<ExtrinsicHash<ChainApi>, ChainApi::Error>>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		let (mempool_results, to_be_submitted) =
+			self.insert_into_mempool(TransactionSource::Local, xts);
+		let mut view_results = view
+			.submit_local_many(to_be_submitted.into_iter().map(|(_, xt)| xt).collect())
+			.into_iter();
+		Some(
+			mempool_results
+				.into_iter()
+				.map(|result| {
+					let tx_hash = result?;
+					let view_result = view_results
+						.next()
+						.expect("Result exists for every transaction submitted to the view. qed");
+					self.mempool.handle_view_submission_result(tx_hash, view_result.map(Into::into))
+				})
+				.collect(),
+		)
+	}
+
+	/// Submits the given transactions to the view at given block hash, stopping at the first
+	/// systemic error.
+	///
+	/// Returns the results gathered until the submission stopped together with the systemic error
+	/// (if any). The transactions are added to the internal mempool first, the ones rejected by
+	/// the view or not submitted due to the systemic error are removed from the mempool again.
+	/// Refer to [`View::submit_many_fail_fast`] for more details.
+	///
+	/// Returns `None` if there is no view at given block.
+	pub async fn submit_many_fail_fast_at(
+		&self,
+		at: Block::Hash,
+		source: TransactionSource,
+		xts: Vec<RawExtrinsicFor<ChainApi>>,
+	) -> Option<(Vec<Result<ExtrinsicHash<ChainApi>, ChainApi::Error>>, Option<ChainApi::Error>)> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		let (mempool_results, to_be_submitted) = self.insert_into_mempool(source, xts);
+		let (view_results, error) = view.submit_many_fail_fast(to_be_submitted).await;
+
+		let mut view_results = view_results.into_iter();
+		let mut results = Vec::with_capacity(mempool_results.len());
+		let mut not_submitted = Vec::new();
+		for result in mempool_results {
+			match result {
+				Ok(tx_hash) if !not_submitted.is_empty() => not_submitted.push(tx_hash),
+				Ok(tx_hash) => match view_results.next() {
+					Some(view_result) => results.push(
+						self.mempool
+							.handle_view_submission_result(tx_hash, view_result.map(Into::into)),
+					),
+					None => not_submitted.push(tx_hash),
+				},
+				Err(error) if not_submitted.is_empty() => results.push(Err(error)),
+				Err(_) => {},
+			}
+		}
+		self.mempool.remove_transactions(&not_submitted);
+		Some((results, error))
+	}
+
+	/// Re-adds the transactions recently removed (and banned) from the view at given block hash.
+	///
+	/// The transactions which are no longer in the internal mempool are added to it first and
+	/// removed from it again if rejected by the view. The transactions still kept in the mempool
+	/// (i.e. removed from the view only) are resubmitted to the view as they are. Refer to
+	/// [`View::resubmit_removed`] for more details.
+	///
+	/// Returns `None` if there is no view at given block.
+	pub async fn resubmit_removed_at(
+		&self,
+		at: Block::Hash,
+		source: TransactionSource,
+		xts: Vec<RawExtrinsicFor<ChainApi>>,
+	) -> Option<Vec<Result<ExtrinsicHash<ChainApi>, ChainApi::Error>>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		let xts = xts
+			.into_iter()
+			.map(|xt| {
+				let tx_hash = self.tx_hash(&xt);
+				(tx_hash, self.mempool.get_by_hash(tx_hash).is_none(), xt)
+			})
+			.collect::<Vec<_>>();
+		let missing = xts.iter().filter(|(_, missing, _)| *missing).map(|(_, _, xt)| xt.clone());
+		let (mempool_results, inserted) = self.insert_into_mempool(source, missing.collect());
+
+		let mut mempool_results = mempool_results.into_iter();
+		let mut inserted = inserted.into_iter();
+		let mut to_be_submitted = Vec::with_capacity(xts.len());
+		let mut submitted = Vec::with_capacity(xts.len());
+		for (tx_hash, missing, xt) in xts {
+			if !missing {
+				let source = TimedTransactionSource::from_transaction_source(source, true);
+				to_be_submitted.push((source, Arc::from(xt)));
+				submitted.push(Ok((tx_hash, false)));
+				continue
+			}
+			match mempool_results
+				.next()
+				.expect("Result exists for every inserted transaction. qed")
+			{
+				Ok(tx_hash) => {
+					to_be_submitted.extend(inserted.next());
+					submitted.push(Ok((tx_hash, true)));
+				},
+				Err(error) => submitted.push(Err(error)),
+			}
+		}
+
+		let mut view_results = view.resubmit_removed(to_be_submitted).await.into_iter();
+		Some(
+			submitted
+				.into_iter()
+				.map(|result| {
+					let (tx_hash, inserted) = result?;
+					let view_result = view_results
+						.next()
+						.expect("Result exists for every transaction submitted to the view. qed")
+						.map(Into::into);
+					if inserted {
+						self.mempool.handle_view_submission_result(tx_hash, view_result)
+					} else {
+						view_result.map(|outcome| {
+							self.mempool.update_transaction_priority(&outcome);
+							outcome.hash()
+						})
+					}
+				})
+				.collect(),
+		)
+	}
+
+	/// Speculatively validates the transaction at the child block of the view at given block hash.
+	///
+	/// The transaction is recorded in the staging set of the view, the live view is not affected.
+	/// Refer to [`View::stage`] for more details.
+	///
+	/// Returns `None` if there is no view at given block.
+	pub async fn stage_at(
+		&self,
+		at: Block::Hash,
+		at_child: Block::Hash,
+		xt: RawExtrinsicFor<ChainApi>,
+	) -> Option<Result<StagedOutcome<ExtrinsicHash<ChainApi>>, ChainApi::Error>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		Some(view.stage(at_child, Arc::from(xt)).await)
+	}
+
+	/// Imports the transactions staged in the view at given block hash into the view.
+	///
+	/// Transactions imported into the view are also inserted into the internal mempool, so they
+	/// are kept in the other views. Transactions rejected by the mempool (e.g. due to its limits)
+	/// are removed from the view again and the mempool error is reported. Refer to
+	/// [`View::commit_staged`] for more details.
+	///
+	/// Returns `None` if there is no view at given block.
+	pub async fn commit_staged_at(
+		&self,
+		at: Block::Hash,
+	) -> Option<Vec<Result<ExtrinsicHash<ChainApi>, ChainApi::Error>>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		let mut results = Vec::new();
+		for result in view.commit_staged().await {
+			let outcome = match result {
+				Ok(outcome) => outcome,
+				Err(error) => {
+					results.push(Err(error));
+					continue
+				},
+			};
+			let tx_hash = outcome.hash();
+			let Some(Some(tx)) = view.transactions_by_hashes(&[tx_hash]).pop() else {
+				results.push(Ok(tx_hash));
+				continue
+			};
+			let (mut mempool_results, _) =
+				self.insert_into_mempool(TransactionSource::External, vec![(*tx.data).clone()]);
+			let mempool_result = mempool_results
+				.pop()
+				.expect("Result exists for every transaction inserted into the mempool. qed")
+				.or_else(|error| match error.into_pool_error() {
+					Ok(TxPoolApiError::AlreadyImported(_)) => Ok(tx_hash),
+					Ok(error) => Err(error.into()),
+					Err(error) => Err(error),
+				});
+			results.push(match mempool_result {
+				Ok(_) => self.mempool.handle_view_submission_result(
+					tx_hash,
+					Ok::<_, ChainApi::Error>(outcome.into()),
+				),
+				Err(error) => {
+					view.force_remove(&tx_hash);
+					Err(error)
+				},
+			});
+		}
+		Some(results)
+	}
+
+	/// Discards the transactions staged in the view at given block hash, returning their hashes.
+	///
+	/// Refer to [`View::discard_staged`] for more details. Returns `None` if there is no view at
+	/// given block.
+	pub fn discard_staged_at(&self, at: Block::Hash) -> Option<Vec<ExtrinsicHash<ChainApi>>> {
+		self.view_store.get_view_at(at, true).map(|(view, _)| view.discard_staged())
+	}
+
+	/// Submits the given transactions to the view at given block hash with the all-or-nothing
+	/// semantics.
+	///
+	/// The transactions are added to the internal mempool first. On the first failure, the
+	/// transactions already submitted within this call are removed from the view and from the
+	/// mempool, and the index of the failing transaction is returned together with the error.
+	/// Refer to [`View::submit_many_atomic`] for more details.
+	///
+	/// Returns `None` if there is no view at given block.
+	pub async fn submit_many_atomic_at(
+		&self,
+		at: Block::Hash,
+		source: TransactionSource,
+		xts: Vec<RawExtrinsicFor<ChainApi>>,
+	) -> Option<Result<Vec<ExtrinsicHash<ChainApi>>, (usize, ChainApi::Error)>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		let (mempool_results, to_be_submitted) = self.insert_into_mempool(source, xts);
+
+		let mut inserted = Vec::with_capacity(to_be_submitted.len());
+		let mut rejected = None;
+		for (index, result) in mempool_results.into_iter().enumerate() {
+			match result {
+				Ok(tx_hash) => inserted.push(tx_hash),
+				Err(error) => {
+					rejected = Some((index, error));
+					break
+				},
+			}
+		}
+		if let Some(rejected) = rejected {
+			self.mempool.remove_transactions(&inserted);
+			return Some(Err(rejected))
+		}
+
+		let result = match view.submit_many_atomic(to_be_submitted).await {
+			Ok(outcomes) => Ok(outcomes
+				.into_iter()
+				.map(|outcome| {
+					let outcome = outcome.into();
+					self.mempool.update_transaction_priority(&outcome);
+					outcome.hash()
+				})
+				.collect()),
+			Err(error) => {
+				self.mempool.remove_transactions(&inserted);
+				Err(error)
+			},
+		};
+		Some(result)
+	}
+
+	/// Submits the given transactions to the view at given block hash, retrying the transient
+	/// failures with the exponential backoff.
+	///
+	/// The transactions are added to the internal mempool first, the ones still failing after
+	/// the last retry are removed from the mempool again. Refer to
+	/// [`View::submit_many_with_retry`] for more details.
+	///
+	/// Returns `None` if there is no view at given block.
+	pub async fn submit_many_with_retry_at(
+		&self,
+		at: Block::Hash,
+		source: TransactionSource,
+		xts: Vec<RawExtrinsicFor<ChainApi>>,
+		max_retries: usize,
+		base_delay: std::time::Duration,
+	) -> Option<Vec<Result<ExtrinsicHash<ChainApi>, ChainApi::Error>>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		let (mempool_results, to_be_submitted) = self.insert_into_mempool(source, xts);
+		let mut view_results = view
+			.submit_many_with_retry(to_be_submitted, max_retries, base_delay)
+			.await
+			.into_iter();
+		Some(
+			mempool_results
+				.into_iter()
+				.map(|result| {
+					let tx_hash = result?;
+					let view_result = view_results
+						.next()
+						.expect("Result exists for every transaction submitted to the view. qed");
+					self.mempool.handle_view_submission_result(tx_hash, view_result.map(Into::into))
+				})
+				.collect(),
+		)
+	}
+
+	/// Moves all the transactions from the view at the `from` block hash into the view at the `to`
+	/// block hash.
+	///
+	/// Refer to [`View::drain_into`] for more details. The transactions which failed to migrate
+	/// to the target view are removed from the internal mempool. Returns the hash of every moved
+	/// transaction together with the result of its submission to the target view, or `None` if
+	/// there is no view at one of the given blocks.
+	pub async fn drain_view_into(
+		&self,
+		from: Block::Hash,
+		to: Block::Hash,
+	) -> Option<Vec<(ExtrinsicHash<ChainApi>, Result<ExtrinsicHash<ChainApi>, ChainApi::Error>)>> {
+		let (source_view, _) = self.view_store.get_view_at(from, true)?;
+		let (target_view, _) = self.view_store.get_view_at(to, true)?;
+		let results = source_view.drain_into(&target_view).await;
+		Some(
+			results
+				.into_iter()
+				.map(|(hash, result)| {
+					(hash, self.mempool.handle_view_submission_result(hash, result.map(Into::into)))
+				})
+				.collect(),
+		)
+	}
+
+	/// Replaces the transaction with the given `old_hash` in the view at given block hash by
+	/// `new_xt`, if the priority of the new transaction is strictly higher.
+	///
+	/// The new transaction is added to the internal mempool first. Once the old transaction is
+	/// replaced in the view, it is removed from the mempool, otherwise the new transaction is
+	/// removed from the mempool again. Refer to [`View::replace_if_better`] for more details.
+	///
+	/// Returns `None` if there is no view at given block.
+	pub async fn replace_if_better_at(
+		&self,
+		at: Block::Hash,
+		old_hash: ExtrinsicHash<ChainApi>,
+		source: TransactionSource,
+		new_xt: RawExtrinsicFor<ChainApi>,
+	) -> Option<Result<ReplaceOutcome, ChainApi::Error>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		let (mut mempool_results, mut to_be_submitted) =
+			self.insert_into_mempool(source, vec![new_xt]);
+		let new_hash = match mempool_results.remove(0) {
+			Ok(new_hash) => new_hash,
+			Err(error) => return Some(Err(error)),
+		};
+
+		let (source, new_xt) = to_be_submitted.remove(0);
+		let outcome = view.replace_if_better(old_hash, source, new_xt).await;
+		let removed =
+			if matches!(outcome, Ok(ReplaceOutcome::Replaced)) { old_hash } else { new_hash };
+		self.mempool.remove_transactions(&[removed]);
+		Some(outcome)
+	}
+
+	/// Removes a single transaction from the view at given block hash, leaving its dependents in
+	/// the view.
+	///
+	/// The removed transaction is also removed from the internal mempool, so it is not submitted
+	/// to the view again on the view update. Refer to [`View::force_remove`] for more details and
+	/// caveats.
+	///
+	/// Returns `true` if the transaction was present in the view.
+	pub fn force_remove_at(&self, at: Block::Hash, tx_hash: &ExtrinsicHash<ChainApi>) -> bool {
+		let found = self
+			.view_store
+			.get_view_at(at, true)
+			.is_some_and(|(v, _)| v.force_remove(tx_hash));
+		if found {
+			self.mempool.remove_transactions(&[*tx_hash]);
+		}
+		found
+	}
+
+	/// Evicts the `count` oldest transactions (together with their dependents) from the view at
+	/// given block hash.
+	///
+	/// The removed transactions are also removed from the internal mempool. Refer to
+	/// [`View::evict_oldest`] for more details. Returns the hashes of all removed transactions, or
+	/// `None` if there is no view at given block.
+	pub fn evict_oldest_at(
+		&self,
+		at: Block::Hash,
+		count: usize,
+	) -> Option<Vec<ExtrinsicHash<ChainApi>>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		let removed = view.evict_oldest(count);
+		self.mempool.remove_transactions(&removed);
+		Some(removed)
+	}
+
+	/// Evicts all the transactions sent by `sender` (together with their dependents) from the view
+	/// at given block hash.
+	///
+	/// The sender of the transaction is provided by the runtime-specific `extract` closure. The
+	/// evicted transactions are also removed from the internal mempool. Refer to
+	/// [`View::evict_by_sender`] for more details. Returns the hashes of all removed
+	/// transactions, or `None` if there is no view at given block.
+	pub fn evict_by_sender_at<AccountId: PartialEq>(
+		&self,
+		at: Block::Hash,
+		extract: impl Fn(&ExtrinsicFor<ChainApi>) -> Option<AccountId>,
+		sender: &AccountId,
+	) -> Option<Vec<ExtrinsicHash<ChainApi>>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		let removed = view.evict_by_sender(extract, sender);
+		self.mempool.remove_transactions(&removed);
+		Some(removed)
+	}
+
+	/// Removes all the transactions from the view at given block hash, keeping the view alive.
+	///
+	/// The removed transactions are also removed from the internal mempool. Refer to
+	/// [`View::clear`] for more details. Returns the hashes of all removed transactions, or `None`
+	/// if there is no view at given block.
+	pub fn clear_at(&self, at: Block::Hash) -> Option<Vec<ExtrinsicHash<ChainApi>>> {
+		let (view, _) = self.view_store.get_view_at(at, true)?;
+		let removed = view.clear();
+		self.mempool.remove_transactions(&removed);
+		Some(removed)
+	}
+
 	/// Returns a best-effort set of ready transactions for a given block, without executing full
 	/// maintain process.
 	///
@@ -541,7 +1367,7 @@ where
 			debug!(
 				target: LOG_TARGET,
 				?at,
-				best_view_hash = ?view.at.hash,
+				best_view_hash = ?view.hash(),
 				before_count,
 				to_be_removed = all_extrinsics.len(),
 				after_count,
@@ -1084,7 +1910,17 @@ where
 			return
 		}
 
-		let best_view = self.view_store.find_best_view(tree_route);
+		let best_view = self.view_store.find_best_view(tree_route).filter(|view| {
+			let requires_rebuild = view.requires_rebuild();
+			if requires_rebuild {
+				debug!(
+					target: LOG_TARGET,
+					origin_view_at = ?view.at,
+					"best view requires rebuild, creating non-cloned view"
+				);
+			}
+			!requires_rebuild
+		});
 		let new_view = self.build_new_view(best_view, hash_and_number, tree_route).await;
 
 		if let Some(view) = new_view {
@@ -1193,10 +2029,22 @@ where
 			?tree_route,
 			"build_new_view"
 		);
+		let cloned_view = origin_view.and_then(|origin_view| {
+			origin_view
+				.try_new_from_other(at)
+				.inspect_err(|error| {
+					warn!(
+						target: LOG_TARGET,
+						?at,
+						origin_view_at = ?origin_view.at,
+						?error,
+						"build_new_view: origin view is not related to the block"
+					);
+				})
+				.ok()
+		});
 		let (mut view, view_dropped_stream, view_aggregated_stream) =
-			if let Some(origin_view) = origin_view {
-				let (mut view, view_dropped_stream, view_aggragated_stream) =
-					View::new_from_other(&origin_view, at);
+			if let Some((mut view, view_dropped_stream, view_aggragated_stream)) = cloned_view {
 				if !tree_route.retracted().is_empty() {
 					view.pool.clear_recently_pruned();
 				}
@@ -1207,30 +2055,34 @@ where
 					?at,
 					"creating non-cloned view"
 				);
-				View::new(
+				let (view, view_dropped_stream, view_aggregated_stream) = View::new(
 					self.api.clone(),
 					at.clone(),
 					self.options.clone(),
 					self.metrics.clone(),
 					self.is_validator.clone(),
+					self.pre_validation_filter.clone(),
+				);
+				(
+					view.with_validation_tracer(self.validation_tracer.clone()),
+					view_dropped_stream,
+					view_aggregated_stream,
 				)
 			};
 
 		let start = Instant::now();
 		// 1. Capture all import notification from the very beginning, so first register all
 		//the listeners.
-		self.import_notification_sink.add_view(
-			view.at.hash,
-			view.pool.validated_pool().import_notification_stream().boxed(),
-		);
+		self.import_notification_sink
+			.add_view(view.hash(), view.pool.validated_pool().import_notification_stream().boxed());
 
 		self.view_store
 			.dropped_stream_controller
-			.add_view(view.at.hash, view_dropped_stream.boxed());
+			.add_view(view.hash(), view_dropped_stream.boxed());
 
 		self.view_store
 			.listener
-			.add_view_aggregated_stream(view.at.hash, view_aggregated_stream.boxed());
+			.add_view_aggregated_stream(view.hash(), view_aggregated_stream.boxed());
 		// sync the transactions statuses and referencing views in all the listeners with newly
 		// cloned view.
 		view.pool.validated_pool().retrigger_notifications();
@@ -1380,7 +2232,7 @@ where
 
 		debug!(
 			target: LOG_TARGET,
-			view_at_hash = ?view.at.hash,
+			view_at_hash = ?view.hash(),
 			submitted_count,
 			mempool_len = self.mempool.len(),
 			"update_view_with_mempool"
@@ -1580,6 +2432,12 @@ where
 
 			let _ = view.pool.resubmit_at(&hash_and_number, resubmit_transactions).await;
 		}
+		view.refresh_status();
+
+		// reorg may remove a large number of transactions from the view, reclaim the memory.
+		if !tree_route.retracted().is_empty() {
+			view.shrink_to_fit();
+		}
 	}
 
 	/// Executes the maintainance for the finalized event.
@@ -1638,6 +2496,39 @@ where
 		self.api.hash_and_length(xt).0
 	}
 
+	/// Adds the transactions submitted to a single view to the internal mempool.
+	///
+	/// Returns the result of the mempool insertion for every transaction (in the order given by
+	/// `xts`), together with the transactions accepted by the mempool which shall be submitted to
+	/// the view. The mempool shall be updated with the results of the view submission (refer to
+	/// [`TxMemPool::handle_view_submission_result`]).
+	fn insert_into_mempool(
+		&self,
+		source: TransactionSource,
+		xts: Vec<RawExtrinsicFor<ChainApi>>,
+	) -> (
+		Vec<Result<ExtrinsicHash<ChainApi>, ChainApi::Error>>,
+		Vec<(TimedTransactionSource, ExtrinsicFor<ChainApi>)>,
+	) {
+		let xts = xts.into_iter().map(Arc::from).collect::<Vec<_>>();
+		let mut accepted = Vec::with_capacity(xts.len());
+		let results = self
+			.mempool
+			.extend_unwatched(source, &xts)
+			.into_iter()
+			.zip(xts)
+			.map(|(result, xt)| {
+				let insertion = result?;
+				self.events_metrics_collector.report_submitted(&insertion);
+				accepted.push((insertion.source, xt));
+				Ok(insertion.hash)
+			})
+			.collect();
+		self.metrics
+			.report(|metrics| metrics.submitted_transactions.inc_by(accepted.len() as _));
+		(results, accepted)
+	}
+
 	/// Attempts to find and replace a lower-priority transaction in the transaction pool with a new
 	/// one.
 	///
@@ -1667,8 +2558,8 @@ where
 		let (xt_hash, validated_tx) = best_view
 			.pool
 			.verify_one(
-				best_view.at.hash,
-				best_view.at.number,
+				best_view.hash(),
+				best_view.number(),
 				TimedTransactionSource::from_transaction_source(source, false),
 				xt.clone(),
 				crate::graph::CheckBannedBeforeVerify::Yes,
@@ -25,6 +25,7 @@ use crate::{
 	LOG_TARGET,
 };
 use futures::{FutureExt, StreamExt};
+use parking_lot::Mutex;
 use prometheus_endpoint::{
 	exponential_buckets, histogram_opts, linear_buckets, register, Counter, Gauge, Histogram,
 	PrometheusError, Registry, U64,
@@ -37,6 +38,7 @@ use std::{
 	collections::{hash_map::Entry, HashMap},
 	future::Future,
 	pin::Pin,
+	sync::Arc,
 	time::{Duration, Instant},
 };
 use tracing::trace;
@@ -79,10 +81,59 @@ pub struct Metrics {
 	pub view_revalidation_invalid_txs: Counter<U64>,
 	/// Total number of valid transactions processed during view revalidation.
 	pub view_revalidation_resubmitted_txs: Counter<U64>,
+	/// Total number of transactions of unknown validity removed during view revalidation
+	/// according to the per-source revalidation policy.
+	pub view_revalidation_removed_by_policy_txs: Counter<U64>,
+	/// Total number of revalidated transactions discarded during view revalidation because they
+	/// were also reported as invalid or of unknown validity.
+	pub view_revalidation_overlapping_txs: Counter<U64>,
+	/// Number of transactions of unknown validity quarantined in the most recently revalidated
+	/// view.
+	pub view_quarantined_txs: Gauge<U64>,
+	/// Total number of view revalidations aborted due to too many consecutive invalid
+	/// transactions.
+	pub view_revalidation_circuit_break: Counter<U64>,
+	/// Total number of views removed without ever completing the background revalidation.
+	pub view_removed_without_revalidation: Counter<U64>,
+	/// Number of view and mempool revalidations currently executed by the background worker.
+	pub revalidations_in_progress: Gauge<U64>,
 	/// Histogram of view revalidation durations.
 	pub view_revalidation_duration: Histogram,
+	/// Histogram of times the view revalidation requests wait in the queue before the
+	/// revalidation starts.
+	pub view_revalidation_queue_latency: Histogram,
+	/// Histogram of ages of the transactions remaining in the views removed on finalization.
+	pub view_tx_final_age: Histogram,
+	/// Histogram of durations of the batch submissions to the views.
+	pub view_submit_many_duration: Histogram,
+	/// Total estimated number of bytes reclaimed by shrinking the views' internal collections.
+	pub view_shrink_reclaimed_bytes: Counter<U64>,
+	/// Total number of the transaction validations served from the views' validation caches.
+	pub view_validation_cache_hits: Counter<U64>,
+	/// Total number of the transaction validations abandoned by the views due to the timeout.
+	pub view_validation_timeouts: Counter<U64>,
+	/// Total number of transactions rejected by the views' batch submission due to their size.
+	pub view_submit_oversized_rejected: Counter<U64>,
+	/// Total number of the views' batch submissions leaving the view filled above the soft limit.
+	pub view_near_capacity: Counter<U64>,
+	/// Total number of transactions rejected by the views' pre-validation filter.
+	pub view_pre_validation_filtered: Counter<U64>,
+	/// Total number of transactions resubmitted to the views after the transient failure.
+	pub view_submit_retries: Counter<U64>,
+	/// Total number of revalidated transactions which could not be reinstated in the view.
+	pub view_revalidation_resubmit_failed: Counter<U64>,
+	/// Total number of transactions removed by the view revalidation due to the elapsed
+	/// longevity, without calling the runtime.
+	pub view_revalidation_expired_txs: Counter<U64>,
 	/// Total number of the views created w/o cloning existing view.
 	pub non_cloned_views: Counter<U64>,
+	/// Total number of the views created by cloning existing view.
+	pub view_clone_total: Counter<U64>,
+	/// The clone depth of the most recently cloned view.
+	///
+	/// The number of ancestor views the view was cloned through. A high depth signals a long
+	/// unfinalized fork.
+	pub view_clone_depth: Gauge<U64>,
 	/// Histograms to track the timing distribution of individual transaction pool events.
 	pub events_histograms: EventsHistograms,
 }
@@ -333,6 +384,48 @@ impl MetricsRegistrant for Metrics {
 				)?,
 				registry,
 			)?,
+			view_revalidation_removed_by_policy_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_view_revalidation_removed_by_policy_txs_total",
+					"Total number of transactions of unknown validity removed during view revalidation according to the per-source revalidation policy.",
+				)?,
+				registry,
+			)?,
+			view_revalidation_overlapping_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_view_revalidation_overlapping_txs_total",
+					"Total number of revalidated transactions discarded during view revalidation because they were also reported as invalid or of unknown validity.",
+				)?,
+				registry,
+			)?,
+			view_quarantined_txs: register(
+				Gauge::new(
+					"substrate_sub_txpool_view_quarantined_txs",
+					"Number of transactions of unknown validity quarantined in the most recently revalidated view.",
+				)?,
+				registry,
+			)?,
+			view_revalidation_circuit_break: register(
+				Counter::new(
+					"substrate_sub_txpool_view_revalidation_circuit_break_total",
+					"Total number of view revalidations aborted due to too many consecutive invalid transactions.",
+				)?,
+				registry,
+			)?,
+			view_removed_without_revalidation: register(
+				Counter::new(
+					"substrate_sub_txpool_view_removed_without_revalidation_total",
+					"Total number of views removed without ever completing the background revalidation.",
+				)?,
+				registry,
+			)?,
+			revalidations_in_progress: register(
+				Gauge::new(
+					"substrate_sub_txpool_revalidations_in_progress",
+					"Number of view and mempool revalidations currently executed by the background worker.",
+				)?,
+				registry,
+			)?,
 			view_revalidation_duration: register(
 				Histogram::with_opts(histogram_opts!(
 					"substrate_sub_txpool_view_revalidation_duration_seconds",
@@ -341,6 +434,93 @@ impl MetricsRegistrant for Metrics {
 				))?,
 				registry,
 			)?,
+			view_revalidation_queue_latency: register(
+				Histogram::with_opts(histogram_opts!(
+					"substrate_sub_txpool_view_revalidation_queue_latency_seconds",
+					"Histogram of times the view revalidation requests wait in the queue before the revalidation starts.",
+					exponential_buckets(0.001, 2.0, 14).unwrap()
+				))?,
+				registry,
+			)?,
+			view_tx_final_age: register(
+				Histogram::with_opts(histogram_opts!(
+					"substrate_sub_txpool_view_tx_final_age_seconds",
+					"Histogram of ages of the transactions remaining in the views removed on finalization.",
+					exponential_buckets(1.0, 2.0, 12).unwrap()
+				))?,
+				registry,
+			)?,
+			view_submit_many_duration: register(
+				Histogram::with_opts(histogram_opts!(
+					"substrate_sub_txpool_view_submit_many_duration_seconds",
+					"Histogram of durations of the batch submissions to the views.",
+					exponential_buckets(0.001, 2.0, 14).unwrap()
+				))?,
+				registry,
+			)?,
+			view_shrink_reclaimed_bytes: register(
+				Counter::new(
+					"substrate_sub_txpool_view_shrink_reclaimed_bytes_total",
+					"Total estimated number of bytes reclaimed by shrinking the views' internal collections.",
+				)?,
+				registry,
+			)?,
+			view_validation_cache_hits: register(
+				Counter::new(
+					"substrate_sub_txpool_view_validation_cache_hits_total",
+					"Total number of the transaction validations served from the views' validation caches.",
+				)?,
+				registry,
+			)?,
+			view_validation_timeouts: register(
+				Counter::new(
+					"substrate_sub_txpool_view_validation_timeouts_total",
+					"Total number of the transaction validations abandoned by the views due to the timeout.",
+				)?,
+				registry,
+			)?,
+			view_submit_oversized_rejected: register(
+				Counter::new(
+					"substrate_sub_txpool_view_submit_oversized_rejected_total",
+					"Total number of transactions rejected by the views' batch submission due to their size.",
+				)?,
+				registry,
+			)?,
+			view_near_capacity: register(
+				Counter::new(
+					"substrate_sub_txpool_view_near_capacity_total",
+					"Total number of the views' batch submissions leaving the view filled above the soft limit.",
+				)?,
+				registry,
+			)?,
+			view_pre_validation_filtered: register(
+				Counter::new(
+					"substrate_sub_txpool_view_pre_validation_filtered_total",
+					"Total number of transactions rejected by the views' pre-validation filter.",
+				)?,
+				registry,
+			)?,
+			view_submit_retries: register(
+				Counter::new(
+					"substrate_sub_txpool_view_submit_retries_total",
+					"Total number of transactions resubmitted to the views after the transient failure.",
+				)?,
+				registry,
+			)?,
+			view_revalidation_resubmit_failed: register(
+				Counter::new(
+					"substrate_sub_txpool_view_revalidation_resubmit_failed_total",
+					"Total number of revalidated transactions which could not be reinstated in the view.",
+				)?,
+				registry,
+			)?,
+			view_revalidation_expired_txs: register(
+				Counter::new(
+					"substrate_sub_txpool_view_revalidation_expired_txs_total",
+					"Total number of transactions removed by the view revalidation due to the elapsed longevity, without calling the runtime.",
+				)?,
+				registry,
+			)?,
 			non_cloned_views: register(
 				Counter::new(
 					"substrate_sub_txpool_non_cloned_views_total",
@@ -348,11 +528,135 @@ impl MetricsRegistrant for Metrics {
 				)?,
 				registry,
 			)?,
+			view_clone_total: register(
+				Counter::new(
+					"substrate_sub_txpool_view_clone_total",
+					"Total number of the views created by cloning existing view.",
+				)?,
+				registry,
+			)?,
+			view_clone_depth: register(
+				Gauge::new(
+					"substrate_sub_txpool_view_clone_depth",
+					"The number of ancestor views the most recently cloned view was cloned through.",
+				)?,
+				registry,
+			)?,
 			events_histograms: EventsHistograms::register(registry)?,
 		}))
 	}
 }
 
+/// Revalidation counters of the views.
+///
+/// Refer to [`RevalidationMetricsAggregator`] for more details.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RevalidationCounters {
+	/// Refer to [`Metrics::view_revalidation_invalid_txs`].
+	pub invalid_txs: u64,
+	/// Refer to [`Metrics::view_revalidation_resubmitted_txs`].
+	pub resubmitted_txs: u64,
+	/// Refer to [`Metrics::view_revalidation_resubmit_failed`].
+	pub resubmit_failed: u64,
+	/// Refer to [`Metrics::view_revalidation_expired_txs`].
+	pub expired_txs: u64,
+}
+
+impl RevalidationCounters {
+	/// Adds the `other` counters to this one.
+	fn accumulate(&mut self, other: &Self) {
+		self.invalid_txs += other.invalid_txs;
+		self.resubmitted_txs += other.resubmitted_txs;
+		self.resubmit_failed += other.resubmit_failed;
+		self.expired_txs += other.expired_txs;
+	}
+
+	/// Increases the Prometheus counters by the values of this instance.
+	fn apply(&self, metrics: &Metrics) {
+		metrics.view_revalidation_invalid_txs.inc_by(self.invalid_txs);
+		metrics.view_revalidation_resubmitted_txs.inc_by(self.resubmitted_txs);
+		metrics.view_revalidation_resubmit_failed.inc_by(self.resubmit_failed);
+		metrics.view_revalidation_expired_txs.inc_by(self.expired_txs);
+	}
+}
+
+/// The buffer of the revalidation counters not yet reported to the Prometheus registry.
+struct AggregationBuffer {
+	/// The counters accumulated since the last flush.
+	pending: RevalidationCounters,
+	/// The moment of the last flush.
+	last_flush: Instant,
+}
+
+/// Reports the revalidation counters of the views to the [`MetricsLink`].
+///
+/// By default the counters are reported immediately. If the flush interval is set (refer to
+/// [`Self::with_flush_interval`]), the updates are accumulated in the buffer shared by all the
+/// clones of the aggregator (i.e. by all the views cloned from each other) and the Prometheus
+/// counters are increased by the aggregate once the interval has elapsed since the last flush. The
+/// interval is checked on every report, there is no background flushing task. Use [`Self::flush`]
+/// to report the buffered updates immediately.
+#[derive(Clone)]
+pub struct RevalidationMetricsAggregator {
+	/// The link to the metrics registry.
+	metrics: MetricsLink,
+	/// The flush interval, `None` means immediate reporting.
+	flush_interval: Option<Duration>,
+	/// The buffer of the updates not yet reported.
+	buffer: Arc<Mutex<AggregationBuffer>>,
+}
+
+impl RevalidationMetricsAggregator {
+	/// Creates the aggregator reporting the counters to given metrics immediately.
+	pub fn new(metrics: MetricsLink) -> Self {
+		Self {
+			metrics,
+			flush_interval: None,
+			buffer: Arc::new(Mutex::new(AggregationBuffer {
+				pending: Default::default(),
+				last_flush: Instant::now(),
+			})),
+		}
+	}
+
+	/// Enables the aggregation of the reported counters, flushed every `flush_interval`.
+	pub fn with_flush_interval(mut self, flush_interval: Duration) -> Self {
+		self.flush_interval = Some(flush_interval);
+		self
+	}
+
+	/// Reports given counters.
+	///
+	/// The counters are either reported immediately, or buffered until the flush interval has
+	/// elapsed.
+	pub fn report(&self, counters: RevalidationCounters) {
+		let Some(flush_interval) = self.flush_interval else {
+			self.metrics.report(|metrics| counters.apply(metrics));
+			return
+		};
+
+		let mut buffer = self.buffer.lock();
+		buffer.pending.accumulate(&counters);
+		if buffer.last_flush.elapsed() >= flush_interval {
+			self.flush_locked(&mut buffer);
+		}
+	}
+
+	/// Reports the buffered counters immediately.
+	pub fn flush(&self) {
+		self.flush_locked(&mut self.buffer.lock());
+	}
+
+	fn flush_locked(&self, buffer: &mut AggregationBuffer) {
+		let pending = std::mem::take(&mut buffer.pending);
+		buffer.last_flush = Instant::now();
+		if pending != RevalidationCounters::default() {
+			trace!(target: LOG_TARGET, ?pending, "revalidation metrics flushed");
+			self.metrics.report(|metrics| pending.apply(metrics));
+		}
+	}
+}
+
 /// Messages used to report and compute event metrics.
 enum EventMetricsMessage<Hash, BlockHash> {
 	/// Message indicating a transaction has been submitted, including the timestamp
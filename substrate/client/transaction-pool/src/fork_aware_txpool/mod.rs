@@ -377,6 +377,10 @@ mod view;
 mod view_store;
 
 pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolTask};
+pub use view::{
+	ImportState, PreValidationFilter, ReplaceOutcome, RevalidationRun, StagedOutcome,
+	ValidationTracer, ViewPin, ViewStats,
+};
 
 mod stream_map_util {
 	use futures::Stream;
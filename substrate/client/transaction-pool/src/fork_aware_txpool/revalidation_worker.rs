@@ -21,15 +21,22 @@
 //! The [*Background tasks*](../index.html#background-tasks) section provides some extra details on
 //! revalidation process.
 
-use std::{marker::PhantomData, pin::Pin, sync::Arc};
+use std::{
+	marker::PhantomData,
+	pin::Pin,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+};
 
 use crate::{graph::ChainApi, LOG_TARGET};
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
 use sp_blockchain::HashAndNumber;
 use sp_runtime::traits::Block as BlockT;
 
-use super::{tx_mem_pool::TxMemPool, view_store::ViewStore};
-use futures::prelude::*;
+use super::{metrics::MetricsLink, tx_mem_pool::TxMemPool, view_store::ViewStore};
+use futures::{future::BoxFuture, prelude::*, stream::FuturesUnordered};
 use tracing::{trace, warn};
 
 use super::view::{FinishRevalidationWorkerChannels, View};
@@ -48,8 +55,51 @@ where
 	RevalidateMempool(Arc<TxMemPool<Api, Block>>, Arc<ViewStore<Api, Block>>, HashAndNumber<Block>),
 }
 
+/// The signal allowing to terminate all the view revalidations at once.
+///
+/// The signal is shared between the [`RevalidationQueue`], the background worker and all the
+/// views being revalidated. Once triggered (e.g. when the node is going down) every ongoing view
+/// revalidation is stopped, and every revalidation started later terminates immediately.
+#[derive(Clone, Default)]
+pub(super) struct RevalidationShutdownSignal {
+	/// Indicates if the signal was already triggered.
+	triggered: Arc<AtomicBool>,
+	/// Used to wake up all the revalidations waiting for the signal.
+	notify: Arc<tokio::sync::Notify>,
+}
+
+impl RevalidationShutdownSignal {
+	/// Triggers the signal, waking up all the revalidations waiting for it.
+	pub(super) fn trigger(&self) {
+		self.triggered.store(true, Ordering::Release);
+		self.notify.notify_waiters();
+	}
+
+	/// Returns true if the signal was already triggered.
+	pub(super) fn is_triggered(&self) -> bool {
+		self.triggered.load(Ordering::Acquire)
+	}
+
+	/// Resolves once the signal is triggered.
+	pub(super) async fn triggered(&self) {
+		// note: `Notified` future is registered before checking the flag, so the notification
+		// sent in between cannot be missed.
+		let notified = self.notify.notified();
+		if self.is_triggered() {
+			return
+		}
+		notified.await
+	}
+}
+
 /// The background revalidation worker.
 struct RevalidationWorker<Block: BlockT> {
+	/// The signal terminating all the view revalidations.
+	shutdown: RevalidationShutdownSignal,
+	/// The maximal number of revalidations executed concurrently.
+	max_concurrent_revalidations: usize,
+	/// Prometheus's metrics endpoint.
+	metrics: MetricsLink,
 	_phantom: PhantomData<Block>,
 }
 
@@ -59,34 +109,66 @@ where
 	<Block as BlockT>::Hash: Unpin,
 {
 	/// Create a new instance of the background worker.
-	fn new() -> Self {
-		Self { _phantom: Default::default() }
+	///
+	/// At least one revalidation is always admitted, even if `max_concurrent_revalidations` is 0.
+	fn new(
+		shutdown: RevalidationShutdownSignal,
+		max_concurrent_revalidations: usize,
+		metrics: MetricsLink,
+	) -> Self {
+		Self {
+			shutdown,
+			max_concurrent_revalidations: max_concurrent_revalidations.max(1),
+			metrics,
+			_phantom: Default::default(),
+		}
 	}
 
 	/// A background worker main loop.
 	///
 	/// Waits for and dispatches the [`WorkerPayload`] messages sent from the
-	/// [`RevalidationQueue`].
+	/// [`RevalidationQueue`]. At most `max_concurrent_revalidations` payloads are processed at
+	/// once, the excess requests are kept in the queue and admitted as the ongoing revalidations
+	/// complete.
 	pub async fn run<Api: ChainApi<Block = Block> + 'static>(
 		self,
 		from_queue: TracingUnboundedReceiver<WorkerPayload<Api, Block>>,
 	) {
 		let mut from_queue = from_queue.fuse();
+		let mut in_progress = FuturesUnordered::new();
 
 		loop {
-			let Some(payload) = from_queue.next().await else {
-				// R.I.P. worker!
-				break;
-			};
-			match payload {
-				WorkerPayload::RevalidateView(view, worker_channels) =>
-					view.revalidate(worker_channels).await,
-				WorkerPayload::RevalidateMempool(
-					mempool,
-					view_store,
-					finalized_hash_and_number,
-				) => mempool.revalidate(view_store, finalized_hash_and_number).await,
-			};
+			let can_admit = in_progress.len() < self.max_concurrent_revalidations;
+			tokio::select! {
+				payload = from_queue.next(), if can_admit => {
+					let Some(payload) = payload else {
+						// R.I.P. worker!
+						break;
+					};
+					in_progress.push(self.dispatch(payload));
+				},
+				Some(()) = in_progress.next(), if !in_progress.is_empty() => {},
+			}
+			self.metrics.report(|metrics| {
+				metrics.revalidations_in_progress.set(in_progress.len() as _);
+			});
+		}
+
+		// complete the revalidations admitted before the queue was closed.
+		while in_progress.next().await.is_some() {}
+	}
+
+	/// Returns the future executing the revalidation requested by given payload.
+	fn dispatch<Api: ChainApi<Block = Block> + 'static>(
+		&self,
+		payload: WorkerPayload<Api, Block>,
+	) -> BoxFuture<'_, ()> {
+		match payload {
+			WorkerPayload::RevalidateView(view, worker_channels) =>
+				async move { view.revalidate(worker_channels, &self.shutdown).await }.boxed(),
+			WorkerPayload::RevalidateMempool(mempool, view_store, finalized_hash_and_number) =>
+				async move { mempool.revalidate(view_store, finalized_hash_and_number).await }
+					.boxed(),
 		}
 	}
 }
@@ -100,6 +182,8 @@ where
 	Block: BlockT,
 {
 	background: Option<TracingUnboundedSender<WorkerPayload<Api, Block>>>,
+	/// The signal terminating all the view revalidations, triggered when the queue is dropped.
+	shutdown: RevalidationShutdownSignal,
 }
 
 impl<Api, Block> RevalidationQueue<Api, Block>
@@ -112,15 +196,27 @@ where
 	///
 	/// All validation requests will be blocking.
 	pub fn new() -> Self {
-		Self { background: None }
+		Self { background: None, shutdown: Default::default() }
 	}
 
 	/// New revalidation queue with background worker.
 	///
-	/// All validation requests will be executed in the background.
-	pub fn new_with_worker() -> (Self, Pin<Box<dyn Future<Output = ()> + Send>>) {
+	/// All validation requests will be executed in the background. At most
+	/// `max_concurrent_revalidations` requests are executed at once, the excess requests are
+	/// queued and admitted as the ongoing revalidations complete. The number of ongoing
+	/// revalidations is reported to the provided `metrics`.
+	pub fn new_with_worker(
+		max_concurrent_revalidations: usize,
+		metrics: MetricsLink,
+	) -> (Self, Pin<Box<dyn Future<Output = ()> + Send>>) {
 		let (to_worker, from_queue) = tracing_unbounded("mpsc_revalidation_queue", 100_000);
-		(Self { background: Some(to_worker) }, RevalidationWorker::new().run(from_queue).boxed())
+		let shutdown = RevalidationShutdownSignal::default();
+		(
+			Self { background: Some(to_worker), shutdown: shutdown.clone() },
+			RevalidationWorker::new(shutdown, max_concurrent_revalidations, metrics)
+				.run(from_queue)
+				.boxed(),
+		)
 	}
 
 	/// Queue the view for later revalidation.
@@ -137,7 +233,7 @@ where
 	) {
 		trace!(
 			target: LOG_TARGET,
-			view_at_hash = ?view.at.hash,
+			view_at_hash = ?view.hash(),
 			"revalidation_queue::revalidate_view: Sending view to revalidation queue"
 		);
 
@@ -153,7 +249,7 @@ where
 				);
 			}
 		} else {
-			view.revalidate(finish_revalidation_worker_channels).await
+			view.revalidate(finish_revalidation_worker_channels, &self.shutdown).await
 		}
 	}
 
@@ -194,13 +290,28 @@ where
 	}
 }
 
+impl<Api, Block> Drop for RevalidationQueue<Api, Block>
+where
+	Api: ChainApi<Block = Block> + 'static,
+	Block: BlockT,
+{
+	/// Terminates all the ongoing and pending view revalidations.
+	///
+	/// The queue is dropped together with the transaction pool when the node is going down, so
+	/// views do not keep on validating transactions until their batches are drained.
+	fn drop(&mut self) {
+		trace!(target: LOG_TARGET, "revalidation_queue: shutting down view revalidations");
+		self.shutdown.trigger();
+	}
+}
+
 #[cfg(test)]
 //todo: add more tests [#5480]
 mod tests {
 	use super::*;
 	use crate::{
 		common::tests::{uxt, TestApi},
-		fork_aware_txpool::view::FinishRevalidationLocalChannels,
+		fork_aware_txpool::{metrics::MetricsLink, view::FinishRevalidationLocalChannels},
 		TimedTransactionSource,
 	};
 	use futures::executor::block_on;
@@ -212,7 +323,15 @@ mod tests {
 		let block0 = api.expect_hash_and_number(0);
 
 		let view = Arc::new(
-			View::new(api.clone(), block0, Default::default(), Default::default(), false.into()).0,
+			View::new(
+				api.clone(),
+				block0,
+				Default::default(),
+				Default::default(),
+				false.into(),
+				None,
+			)
+			.0,
 		);
 		let queue = Arc::new(RevalidationQueue::new());
 
@@ -249,4 +368,148 @@ mod tests {
 		// number of ready
 		assert_eq!(view.status().ready, 1);
 	}
+
+	#[test]
+	fn revalidation_worker_admits_queued_requests() {
+		let api = Arc::new(TestApi::default());
+		let block0 = api.expect_hash_and_number(0);
+
+		let registry = prometheus_endpoint::Registry::new();
+		let (queue, mut worker) =
+			RevalidationQueue::new_with_worker(2, MetricsLink::new(Some(&registry)));
+		let queue = Arc::new(queue);
+
+		let xt = uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce: 0,
+		});
+		let views = (0..3)
+			.map(|_| {
+				let view = View::new(
+					api.clone(),
+					block0.clone(),
+					Default::default(),
+					Default::default(),
+					false.into(),
+					None,
+				)
+				.0;
+				block_on(view.submit_one(
+					TimedTransactionSource::new_external(false),
+					Arc::from(xt.clone()),
+				))
+				.unwrap();
+				Arc::new(view)
+			})
+			.collect::<Vec<_>>();
+		// the validation never finishes, so the admitted revalidations hold the worker slots.
+		api.stall_validation.lock().insert(api.hash_and_length(&xt).0);
+		let validation_requests = api.validation_requests().len();
+		let in_progress = || {
+			registry
+				.gather()
+				.iter()
+				.find(|family| {
+					family.get_name() == "substrate_sub_txpool_revalidations_in_progress"
+				})
+				.map(|family| family.get_metric()[0].get_gauge().get_value())
+		};
+
+		// three requests exceed the limit, the excess one stays queued.
+		for view in &views {
+			block_on(View::start_background_revalidation(view.clone(), queue.clone()));
+		}
+		assert!(worker.as_mut().now_or_never().is_none());
+		assert_eq!(in_progress(), Some(2.0));
+		assert_eq!(api.validation_requests().len(), validation_requests + 2);
+
+		// the queued request is admitted once others complete.
+		for view in &views {
+			block_on(future::select(worker.as_mut(), Box::pin(view.finish_revalidation())));
+		}
+		assert_eq!(api.validation_requests().len(), validation_requests + 3);
+		assert!(worker.as_mut().now_or_never().is_none());
+		assert_eq!(in_progress(), Some(0.0));
+	}
+
+	#[test]
+	fn revalidation_reports_queue_latency() {
+		let api = Arc::new(TestApi::default());
+		let block0 = api.expect_hash_and_number(0);
+
+		let registry = prometheus_endpoint::Registry::new();
+		let metrics = MetricsLink::new(Some(&registry));
+		let view = Arc::new(
+			View::new(api.clone(), block0, Default::default(), metrics, false.into(), None).0,
+		);
+		let (queue, mut worker) = RevalidationQueue::new_with_worker(1, Default::default());
+		let queue = Arc::new(queue);
+
+		// the request waits in the queue until the worker is polled.
+		block_on(View::start_background_revalidation(view.clone(), queue.clone()));
+		std::thread::sleep(std::time::Duration::from_millis(20));
+		assert!(worker.as_mut().now_or_never().is_none());
+		block_on(view.finish_revalidation());
+
+		let latency = registry
+			.gather()
+			.iter()
+			.find(|family| {
+				family.get_name() == "substrate_sub_txpool_view_revalidation_queue_latency_seconds"
+			})
+			.map(|family| family.get_metric()[0].get_histogram().clone())
+			.unwrap();
+		assert_eq!(latency.get_sample_count(), 1);
+		assert!(latency.get_sample_sum() >= 0.02);
+	}
+
+	#[test]
+	fn revalidation_queue_respects_shutdown_signal() {
+		let api = Arc::new(TestApi::default());
+		let block0 = api.expect_hash_and_number(0);
+
+		let view = Arc::new(
+			View::new(
+				api.clone(),
+				block0,
+				Default::default(),
+				Default::default(),
+				false.into(),
+				None,
+			)
+			.0,
+		);
+		let queue = Arc::new(RevalidationQueue::new());
+
+		let uxt = uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce: 0,
+		});
+
+		let _ = block_on(view.submit_many(std::iter::once((
+			TimedTransactionSource::new_external(false),
+			uxt.clone().into(),
+		))));
+		assert_eq!(api.validation_requests().len(), 1);
+
+		let (_finish_revalidation_request_tx, finish_revalidation_request_rx) =
+			tokio::sync::mpsc::channel(1);
+		let (revalidation_result_tx, _revalidation_result_rx) = tokio::sync::mpsc::channel(1);
+
+		let finish_revalidation_worker_channels = FinishRevalidationWorkerChannels::new(
+			finish_revalidation_request_rx,
+			revalidation_result_tx,
+		);
+
+		queue.shutdown.trigger();
+		block_on(queue.revalidate_view(view.clone(), finish_revalidation_worker_channels));
+
+		// no revalidation was performed
+		assert_eq!(api.validation_requests().len(), 1);
+		assert_eq!(view.status().ready, 1);
+	}
 }
@@ -628,6 +628,27 @@ where
 		});
 	}
 
+	/// Updates the mempool with the result of submitting the transaction to a single view.
+	///
+	/// The transaction rejected by the view is removed from the mempool, otherwise its priority is
+	/// updated. Intended for the submissions bypassing the view store.
+	pub(super) fn handle_view_submission_result<E>(
+		&self,
+		tx_hash: ExtrinsicHash<ChainApi>,
+		result: Result<ViewStoreSubmitOutcome<ChainApi>, E>,
+	) -> Result<ExtrinsicHash<ChainApi>, E> {
+		match result {
+			Ok(outcome) => {
+				self.update_transaction_priority(&outcome);
+				Ok(outcome.hash())
+			},
+			Err(error) => {
+				self.remove_transactions(&[tx_hash]);
+				Err(error)
+			},
+		}
+	}
+
 	/// Counts the number of transactions in the provided iterator of hashes
 	/// that are not known to the pool.
 	pub(super) fn count_unknown_transactions<'a>(
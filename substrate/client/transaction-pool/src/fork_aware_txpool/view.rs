@@ -23,31 +23,78 @@
 //!
 //! Refer to [*View*](../index.html#view) section for more details.
 
-use super::metrics::MetricsLink as PrometheusMetrics;
+use super::{
+	metrics::{
+		MetricsLink as PrometheusMetrics, RevalidationCounters, RevalidationMetricsAggregator,
+	},
+	revalidation_worker::RevalidationShutdownSignal,
+};
 use crate::{
 	common::tracing_log_xt::log_xt_trace,
 	graph::{
 		self, base_pool::TimedTransactionSource, BlockHash, ExtrinsicFor, ExtrinsicHash,
-		IsValidator, TransactionFor, ValidatedPoolSubmitOutcome, ValidatedTransaction,
+		IsValidator, NumberFor, TransactionFor, ValidatedPoolSubmitOutcome, ValidatedTransaction,
 		ValidatedTransactionFor,
 	},
 	LOG_TARGET,
 };
+use futures::{Stream, StreamExt};
 use indexmap::IndexMap;
 use parking_lot::Mutex;
-use sc_transaction_pool_api::{error::Error as TxPoolError, PoolStatus, TransactionStatus};
+use sc_transaction_pool_api::{
+	error::{Error as TxPoolError, IntoPoolError},
+	PoolStatus, ReadyTransactions, TransactionStatus,
+};
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
 use sp_blockchain::HashAndNumber;
 use sp_runtime::{
-	generic::BlockId, traits::Block as BlockT, transaction_validity::TransactionValidityError,
-	SaturatedConversion,
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT},
+	transaction_validity::{
+		InvalidTransaction, TransactionPriority, TransactionSource, TransactionTag,
+		TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	SaturatedConversion, Weight,
 };
-use std::{sync::Arc, time::Instant};
-use tracing::{debug, trace};
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{
+		atomic::{AtomicBool, AtomicUsize, Ordering},
+		Arc,
+	},
+	time::{Duration, Instant},
+};
+use tracing::{debug, trace, warn, Instrument};
 
 pub(super) struct RevalidationResult<ChainApi: graph::ChainApi> {
 	revalidated: IndexMap<ExtrinsicHash<ChainApi>, ValidatedTransactionFor<ChainApi>>,
+	/// Transactions found to be invalid, reported to the listener as `Invalid`.
 	invalid_hashes: Vec<ExtrinsicHash<ChainApi>>,
+	/// Transactions of unknown validity (or which failed to be revalidated), reported to the
+	/// listener as `Dropped`.
+	unknown_hashes: Vec<ExtrinsicHash<ChainApi>>,
+	/// Number of the transactions of unknown validity which removal was decided by the
+	/// revalidation policies (rather than by the default behavior).
+	removed_by_policy: u64,
+	/// The quarantine of the view to be set when the result is applied.
+	///
+	/// `None` if the quarantine shall be left intact (e.g. no transactions were revalidated).
+	quarantine: Option<HashMap<ExtrinsicHash<ChainApi>, usize>>,
+}
+
+impl<ChainApi: graph::ChainApi> RevalidationResult<ChainApi> {
+	/// Discards the revalidated transactions which are also reported as invalid or unknown.
+	///
+	/// The removal wins: such transactions are removed from the view and are not resubmitted.
+	/// The order of the remaining revalidated transactions is retained. Returns the number of
+	/// discarded transactions.
+	fn discard_overlapping(&mut self) -> usize {
+		let revalidated_len = self.revalidated.len();
+		for tx_hash in self.invalid_hashes.iter().chain(self.unknown_hashes.iter()) {
+			self.revalidated.shift_remove(tx_hash);
+		}
+		revalidated_len - self.revalidated.len()
+	}
 }
 
 /// Used to obtain result from RevalidationWorker on View side.
@@ -99,18 +146,46 @@ pub(super) struct FinishRevalidationWorkerChannels<ChainApi: graph::ChainApi> {
 	finish_revalidation_request_rx: FinishRevalidationRequestReceiver,
 	/// Used to send revalidation results.
 	revalidation_result_tx: RevalidationResultSender<ChainApi>,
+	/// The instant the revalidation was requested (i.e. the request was put into the queue).
+	requested_at: Instant,
 }
 
 impl<ChainApi: graph::ChainApi> FinishRevalidationWorkerChannels<ChainApi> {
 	/// Creates a new instance of endpoints for channels used on `RevalidationWorker` side
+	///
+	/// The revalidation request is timestamped with the current instant.
 	pub fn new(
 		finish_revalidation_request_rx: FinishRevalidationRequestReceiver,
 		revalidation_result_tx: RevalidationResultSender<ChainApi>,
 	) -> Self {
-		Self { finish_revalidation_request_rx, revalidation_result_tx }
+		Self {
+			finish_revalidation_request_rx,
+			revalidation_result_tx,
+			requested_at: Instant::now(),
+		}
 	}
 }
 
+/// Maximal time the maintain thread waits for the finish revalidation request to be sent to the
+/// background revalidation worker, and then for the revalidation result to be received from it.
+const FINISH_REVALIDATION_REQUEST_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Minimal interval between the warnings about the view approaching its capacity.
+///
+/// Refer to [`graph::Options::soft_limit_ratio`] for more details.
+const NEAR_CAPACITY_WARN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The name of the tracing event emitted when the revalidation results are applied to the view.
+///
+/// The event is emitted at `debug` level with the following fields:
+/// - `at_hash`: the hash of the block the view is associated with,
+/// - `at_number`: the number of the block the view is associated with,
+/// - `invalid`: the number of transactions removed from the view,
+/// - `revalidated`: the number of revalidated transactions resubmitted to the view,
+/// - `duration_secs`: the time of applying the results, in seconds,
+/// - `duration`: the human-readable time of applying the results.
+const REVALIDATION_APPLIED_EVENT: &str = "RevalidationApplied";
+
 /// Single event used in aggregated stream. Tuple containing hash of transactions and its status.
 pub(super) type TransactionStatusEvent<H, BH> = (H, TransactionStatus<H, BH>);
 /// Warning threshold for (unbounded) channel used in aggregated view's streams.
@@ -150,7 +225,6 @@ pub(super) struct ViewPoolObserver<ChainApi: graph::ChainApi> {
 impl<C: graph::ChainApi> graph::EventHandler<C> for ViewPoolObserver<C> {
 	// note: skipped, notified by ForkAwareTxPool directly to multi view listener.
 	fn broadcasted(&self, _: ExtrinsicHash<C>, _: Vec<String>) {}
-	fn dropped(&self, _: ExtrinsicHash<C>) {}
 	fn finalized(&self, _: ExtrinsicHash<C>, _: BlockHash<C>, _: usize) {}
 	fn retracted(&self, _: ExtrinsicHash<C>, _: BlockHash<C>) {
 		// note: [#5479], we do not send to aggregated stream.
@@ -180,6 +254,10 @@ impl<C: graph::ChainApi> graph::EventHandler<C> for ViewPoolObserver<C> {
 		self.send_to_dropped_stream_sink(tx, TransactionStatus::Invalid);
 	}
 
+	fn dropped(&self, tx: ExtrinsicHash<C>) {
+		self.send_to_dropped_stream_sink(tx, TransactionStatus::Dropped);
+	}
+
 	fn pruned(&self, tx: ExtrinsicHash<C>, block_hash: BlockHash<C>, tx_index: usize) {
 		self.send_to_aggregated_stream_sink(tx, TransactionStatus::InBlock((block_hash, tx_index)));
 	}
@@ -237,6 +315,118 @@ impl<ChainApi: graph::ChainApi> ViewPoolObserver<ChainApi> {
 	}
 }
 
+/// A snapshot of the view's internal pool statistics.
+///
+/// All the pool related figures are gathered under a single acquisition of the internal pool lock,
+/// so they are consistent with each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewStats {
+	/// Number of transactions in the ready queue.
+	pub ready: usize,
+	/// Number of transactions in the future queue.
+	pub future: usize,
+	/// Estimated number of bytes of all (ready and future) transaction encodings.
+	pub total_bytes: usize,
+	/// The age of the oldest transaction in the view.
+	///
+	/// `None` if the view is empty or none of the transactions carries a timestamp.
+	pub oldest_tx_age: Option<Duration>,
+	/// Indicates if the background revalidation of the view is currently running.
+	pub revalidation_in_progress: bool,
+	/// Number of transactions of unknown validity kept in the view's quarantine.
+	///
+	/// Refer to [`graph::Options::unknown_quarantine_max_retries`] for more details.
+	pub quarantined: usize,
+	/// The number of ancestor views the view was cloned through.
+	///
+	/// Zero for the view created w/o cloning existing view.
+	pub clone_depth: usize,
+}
+
+/// The outcome of the budgeted view revalidation.
+///
+/// Refer to [`View::revalidate_with_budget`] for more details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevalidationRun {
+	/// Number of transactions validated by the runtime.
+	pub validated: usize,
+	/// Number of transactions found to be invalid (and removed from the view).
+	pub invalid: usize,
+	/// The part of the budget which was not used.
+	pub remaining_budget: usize,
+}
+
+/// The outcome of the conditional transaction replacement.
+///
+/// Refer to [`View::replace_if_better`] for more details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplaceOutcome {
+	/// The old transaction was replaced by the new one.
+	Replaced,
+	/// The priority of the new transaction is not strictly higher than the priority of the old
+	/// one, the view was left unchanged.
+	NotBetter,
+	/// The old transaction is not present in the view, the view was left unchanged.
+	NotFound,
+}
+
+/// The state of the transaction within the view.
+///
+/// Refer to [`View::import_state`] for more details.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportState {
+	/// The transaction is in the ready queue.
+	Ready,
+	/// The transaction is in the future queue.
+	Future,
+	/// The transaction is not imported, but it is temporarily banned.
+	Banned,
+	/// The transaction is not known to the view.
+	Unknown,
+}
+
+/// The outcome of the speculative submission.
+///
+/// Refer to [`View::stage`] for more details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagedOutcome<Hash> {
+	/// The hash of the staged transaction.
+	pub hash: Hash,
+	/// The validity of the transaction at the child block.
+	pub validity: ValidTransaction,
+}
+
+/// A cheap, local predicate checked against the transaction before it is validated by the
+/// runtime.
+///
+/// Transactions rejected by the predicate are not imported into the view, the returned error is
+/// reported to the submitter. Allows to enforce the local policies (e.g. size caps or call-type
+/// filters) without calling into the runtime.
+pub type PreValidationFilter<ChainApi> =
+	Arc<dyn Fn(&ExtrinsicFor<ChainApi>) -> Result<(), TxPoolError> + Send + Sync>;
+
+/// A hook observing the runtime validations performed by the view.
+///
+/// Invoked with the hash of transaction and the duration of its validation after every
+/// validation done by the view revalidation and by the local submission. Intended for profiling
+/// (e.g. sampling the slowest validations), so it shall be cheap: it is called synchronously on
+/// the validation path.
+pub type ValidationTracer<ChainApi> = Arc<dyn Fn(ExtrinsicHash<ChainApi>, Duration) + Send + Sync>;
+
+/// A guard keeping the view pinned.
+///
+/// Pinned view is not removed from the pool on finalization, refer to [`View::pin`] for more
+/// details. The view is unpinned when the guard is dropped.
+pub struct ViewPin<ChainApi: graph::ChainApi> {
+	view: Arc<View<ChainApi>>,
+}
+
+impl<ChainApi: graph::ChainApi> Drop for ViewPin<ChainApi> {
+	fn drop(&mut self) {
+		self.view.unpin();
+	}
+}
+
 /// Represents the state of transaction pool for given block.
 ///
 /// Refer to [*View*](../index.html#view) section for more details on the purpose and life cycle of
@@ -250,6 +440,46 @@ pub(super) struct View<ChainApi: graph::ChainApi> {
 	revalidation_worker_channels: Mutex<Option<FinishRevalidationLocalChannels<ChainApi>>>,
 	/// Prometheus's metrics endpoint.
 	metrics: PrometheusMetrics,
+	/// Reports the revalidation counters, possibly aggregated.
+	///
+	/// Refer to [`graph::Options::revalidation_metrics_flush_interval`] for more details.
+	revalidation_metrics: RevalidationMetricsAggregator,
+	/// Indicates that the view shall not be cloned, but the new view shall be built from scratch.
+	///
+	/// Set when the revalidation was aborted due to too many consecutive invalid transactions.
+	requires_rebuild: AtomicBool,
+	/// The instant at which the result of the most recent background revalidation was applied to
+	/// the view. `None` if the view was never revalidated.
+	last_revalidation: Mutex<Option<Instant>>,
+	/// Optional predicate checked before the transaction is validated by the runtime.
+	pre_validation_filter: Option<PreValidationFilter<ChainApi>>,
+	/// Optional hook observing the runtime validations.
+	validation_tracer: Option<ValidationTracer<ChainApi>>,
+	/// The number of alive [`ViewPin`] guards. Pinned view is not removed on finalization.
+	pins: AtomicUsize,
+	/// Transactions of unknown validity kept in the view, with the number of revalidations they
+	/// were retried in.
+	///
+	/// Refer to [`graph::Options::unknown_quarantine_max_retries`] for more details.
+	quarantine: Mutex<HashMap<ExtrinsicHash<ChainApi>, usize>>,
+	/// Sinks of the streams of transactions removed from the view by the revalidation.
+	revalidation_removed_sinks: Mutex<Vec<TracingUnboundedSender<ExtrinsicHash<ChainApi>>>>,
+	/// The instant of the last warning about the view approaching its capacity.
+	last_near_capacity_warning: Mutex<Option<Instant>>,
+	/// The sender of the watch channel carrying the most recent status of the view.
+	status_sink: tokio::sync::watch::Sender<PoolStatus>,
+	/// Indicates that the view does not accept new transactions anymore.
+	///
+	/// Set when the view was finalized, refer to [`Self::on_finalized`].
+	frozen: AtomicBool,
+	/// Transactions validated at the child block, not yet imported into the view.
+	///
+	/// Refer to [`Self::stage`] for more details.
+	staged: Mutex<Vec<(ExtrinsicHash<ChainApi>, ExtrinsicFor<ChainApi>)>>,
+	/// The number of ancestor views the view was cloned through.
+	///
+	/// Refer to [`ViewStats::clone_depth`] for more details.
+	clone_depth: usize,
 }
 
 impl<ChainApi> View<ChainApi>
@@ -264,6 +494,7 @@ where
 		options: graph::Options,
 		metrics: PrometheusMetrics,
 		is_validator: IsValidator,
+		pre_validation_filter: Option<PreValidationFilter<ChainApi>>,
 	) -> (
 		Self,
 		DroppedMonitoringStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
@@ -271,6 +502,11 @@ where
 	) {
 		metrics.report(|metrics| metrics.non_cloned_views.inc());
 		let (event_handler, dropped_stream, aggregated_stream) = ViewPoolObserver::new();
+		let revalidation_metrics = RevalidationMetricsAggregator::new(metrics.clone());
+		let revalidation_metrics = match options.revalidation_metrics_flush_interval {
+			Some(flush_interval) => revalidation_metrics.with_flush_interval(flush_interval),
+			None => revalidation_metrics,
+		};
 		(
 			Self {
 				pool: graph::Pool::new_with_event_handler(
@@ -281,6 +517,24 @@ where
 				),
 				at,
 				revalidation_worker_channels: Mutex::from(None),
+				requires_rebuild: AtomicBool::new(false),
+				frozen: AtomicBool::new(false),
+				staged: Default::default(),
+				clone_depth: 0,
+				pins: AtomicUsize::new(0),
+				quarantine: Default::default(),
+				revalidation_removed_sinks: Default::default(),
+				last_near_capacity_warning: Default::default(),
+				last_revalidation: Mutex::from(None),
+				pre_validation_filter,
+				validation_tracer: None,
+				status_sink: tokio::sync::watch::Sender::new(PoolStatus {
+					ready: 0,
+					ready_bytes: 0,
+					future: 0,
+					future_bytes: 0,
+				}),
+				revalidation_metrics,
 				metrics,
 			},
 			dropped_stream,
@@ -288,7 +542,23 @@ where
 		)
 	}
 
+	/// Disables the metrics reporting of the view.
+	///
+	/// Installs the null metrics sink: the closures passed to the metrics `report` calls on the hot
+	/// paths (e.g. revalidation) are never invoked. Intended for benchmarking, so the results are
+	/// not skewed by the metrics updates. Views cloned from this view inherit the null sink.
+	#[cfg(test)]
+	pub(super) fn with_metrics_disabled(mut self) -> Self {
+		self.metrics = Default::default();
+		self.revalidation_metrics = RevalidationMetricsAggregator::new(Default::default());
+		self
+	}
+
 	/// Creates a copy of the other view.
+	///
+	/// The block `at` is expected to be related to the block of the other view, refer to
+	/// [`Self::check_related`]. This is asserted in debug builds only, use
+	/// [`Self::try_new_from_other`] for the checked variant.
 	pub(super) fn new_from_other(
 		&self,
 		at: &HashAndNumber<ChainApi::Block>,
@@ -296,49 +566,511 @@ where
 		Self,
 		DroppedMonitoringStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
 		AggregatedStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
+	) {
+		debug_assert!(
+			self.check_related(at).is_ok(),
+			"view at {:?} cannot be created from the view at unrelated block {:?}",
+			at,
+			self.at,
+		);
+		self.new_from_other_unchecked(at)
+	}
+
+	/// Creates a copy of the other view, provided the block `at` is related to the block of the
+	/// other view.
+	///
+	/// Returns an error if the relationship check fails, refer to [`Self::check_related`].
+	pub(super) fn try_new_from_other(
+		&self,
+		at: &HashAndNumber<ChainApi::Block>,
+	) -> Result<
+		(
+			Self,
+			DroppedMonitoringStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
+			AggregatedStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
+		),
+		ChainApi::Error,
+	> {
+		self.check_related(at)?;
+		Ok(self.new_from_other_unchecked(at))
+	}
+
+	/// Checks if the block `at` is related to the block of this view.
+	///
+	/// The number of `at` shall match the number of the block known to the chain API, and the
+	/// tree route between the blocks shall exist (meaning that both blocks are known and share
+	/// the common ancestor).
+	fn check_related(&self, at: &HashAndNumber<ChainApi::Block>) -> Result<(), ChainApi::Error> {
+		let api = self.pool.validated_pool().api();
+		if api.block_id_to_number(&BlockId::Hash(at.hash))? != Some(at.number) {
+			return Err(TxPoolError::InvalidBlockId(format!("{:?}", at)).into())
+		}
+		api.tree_route(self.at.hash, at.hash).map(drop)
+	}
+
+	fn new_from_other_unchecked(
+		&self,
+		at: &HashAndNumber<ChainApi::Block>,
+	) -> (
+		Self,
+		DroppedMonitoringStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
+		AggregatedStream<ExtrinsicHash<ChainApi>, BlockHash<ChainApi>>,
 	) {
 		let (event_handler, dropped_stream, aggregated_stream) = ViewPoolObserver::new();
+		let pool = self.pool.deep_clone_with_event_handler(event_handler);
+		let status_sink = tokio::sync::watch::Sender::new(pool.validated_pool().status());
+		let clone_depth = self.clone_depth + 1;
+		self.metrics.report(|metrics| {
+			metrics.view_clone_total.inc();
+			metrics.view_clone_depth.set(clone_depth as _);
+		});
 		(
 			View {
 				at: at.clone(),
-				pool: self.pool.deep_clone_with_event_handler(event_handler),
+				pool,
 				revalidation_worker_channels: Mutex::from(None),
+				requires_rebuild: AtomicBool::new(false),
+				frozen: AtomicBool::new(false),
+				staged: Default::default(),
+				clone_depth,
+				pins: AtomicUsize::new(0),
+				quarantine: Mutex::from(self.quarantine.lock().clone()),
+				revalidation_removed_sinks: Default::default(),
+				last_near_capacity_warning: Default::default(),
+				last_revalidation: Mutex::from(None),
+				pre_validation_filter: self.pre_validation_filter.clone(),
+				validation_tracer: self.validation_tracer.clone(),
+				status_sink,
 				metrics: self.metrics.clone(),
+				revalidation_metrics: self.revalidation_metrics.clone(),
 			},
 			dropped_stream,
 			aggregated_stream,
 		)
 	}
 
+	/// Sets the hook observing the runtime validations performed by the view.
+	///
+	/// The hook is inherited by the views cloned from this view. Refer to [`ValidationTracer`]
+	/// for more details.
+	pub(super) fn with_validation_tracer(
+		mut self,
+		tracer: Option<ValidationTracer<ChainApi>>,
+	) -> Self {
+		self.validation_tracer = tracer;
+		self
+	}
+
+	/// Returns the instant the traced validation started at, `None` if there is no tracer.
+	fn validation_trace_start(&self) -> Option<Instant> {
+		self.validation_tracer.as_ref().map(|_| Instant::now())
+	}
+
+	/// Reports the duration of the validation started at `started` to the tracer (if any).
+	fn trace_validation(&self, tx_hash: ExtrinsicHash<ChainApi>, started: Option<Instant>) {
+		if let (Some(tracer), Some(started)) = (self.validation_tracer.as_ref(), started) {
+			tracer(tx_hash, started.elapsed());
+		}
+	}
+
+	/// Reports the validation cache hits and the timed out validations accumulated since the last
+	/// report to the metrics.
+	fn report_validation_counters(&self) {
+		let validated_pool = self.pool.validated_pool();
+		let hits = validated_pool.take_validation_cache_hits();
+		if hits > 0 {
+			self.metrics.report(|metrics| metrics.view_validation_cache_hits.inc_by(hits));
+		}
+		let timeouts = validated_pool.take_validation_timeouts();
+		if timeouts > 0 {
+			self.metrics.report(|metrics| metrics.view_validation_timeouts.inc_by(timeouts));
+		}
+	}
+
 	/// Imports single unvalidated extrinsic into the view.
 	pub(super) async fn submit_one(
 		&self,
 		source: TimedTransactionSource,
 		xt: ExtrinsicFor<ChainApi>,
 	) -> Result<ValidatedPoolSubmitOutcome<ChainApi>, ChainApi::Error> {
-		self.submit_many(std::iter::once((source, xt)))
-			.await
-			.pop()
-			.expect("There is exactly one result, qed.")
+		self.pre_validate(&xt)?;
+		trace!(
+			target: LOG_TARGET,
+			tx_hash = ?self.pool.validated_pool().api().hash_and_length(&xt).0,
+			at_hash = ?self.at.hash,
+			"view::submit_one"
+		);
+		let result = self.pool.submit_one(&self.at, source, xt).await;
+		self.report_validation_counters();
+		self.refresh_status();
+		result
 	}
 
 	/// Imports many unvalidated extrinsics into the view.
+	///
+	/// The results are always returned in the order given by `xts`: the result at index `i`
+	/// corresponds to the `i`-th transaction, regardless of how the transactions are validated
+	/// internally. Callers rely on this positional correlation, so it shall be retained if the
+	/// validation is ever parallelized.
+	///
+	/// Transactions exceeding [`graph::Options::max_extrinsic_size`] (if set) or rejected by the
+	/// pre-validation filter (if any) are not validated, the error is returned at their position
+	/// instead.
 	pub(super) async fn submit_many(
 		&self,
 		xts: impl IntoIterator<Item = (TimedTransactionSource, ExtrinsicFor<ChainApi>)>,
 	) -> Vec<Result<ValidatedPoolSubmitOutcome<ChainApi>, ChainApi::Error>> {
+		let max_size = self.pool.validated_pool().options().max_extrinsic_size;
+		if self.pre_validation_filter.is_none() && max_size.is_none() && !self.is_frozen() {
+			return self.submit_many_unfiltered(xts).await
+		}
+
+		let mut rejections = Vec::new();
+		let xts = xts
+			.into_iter()
+			.filter(|(_, xt)| {
+				let rejection = max_size
+					.map_or(Ok(()), |max_size| self.check_size(xt, max_size))
+					.and_then(|_| self.pre_validate(xt))
+					.err();
+				let passed = rejection.is_none();
+				rejections.push(rejection);
+				passed
+			})
+			.collect::<Vec<_>>();
+
+		let mut results = self.submit_many_unfiltered(xts).await.into_iter();
+		rejections
+			.into_iter()
+			.map(|rejection| match rejection {
+				Some(error) => Err(error),
+				None => results.next().expect("Result exists for every passed transaction. qed"),
+			})
+			.collect()
+	}
+
+	/// Imports many unvalidated extrinsics into the view, retrying the transient failures.
+	///
+	/// Works as [`Self::submit_many`], but the transactions failing with the retryable error
+	/// (refer to [`Self::is_retryable`]) are submitted again, at most `max_retries` times. The
+	/// delay before the consecutive retries grows exponentially, starting from `base_delay`.
+	/// Permanent failures (e.g. invalid transactions) are not retried. The results are returned in
+	/// the order given by `xts`, the result of the last attempt is returned for the retried
+	/// transactions.
+	///
+	/// Note: the unknown validity is never cached (refer to
+	/// [`graph::Options::validation_cache_size`]), so the retries always call into the runtime.
+	pub(super) async fn submit_many_with_retry(
+		&self,
+		xts: Vec<(TimedTransactionSource, ExtrinsicFor<ChainApi>)>,
+		max_retries: usize,
+		base_delay: Duration,
+	) -> Vec<Result<ValidatedPoolSubmitOutcome<ChainApi>, ChainApi::Error>> {
+		let mut results = self.submit_many(xts.iter().cloned()).await;
+		for retry in 0..max_retries {
+			let mut to_retry = Vec::new();
+			results = results
+				.into_iter()
+				.enumerate()
+				.map(|(index, result)| {
+					result.map_err(|error| {
+						let (error, retryable) = Self::is_retryable(error);
+						if retryable {
+							to_retry.push(index);
+						}
+						error
+					})
+				})
+				.collect();
+			if to_retry.is_empty() {
+				break
+			}
+
+			let delay = base_delay.saturating_mul(2u32.saturating_pow(retry as u32));
+			debug!(
+				target: LOG_TARGET,
+				count = to_retry.len(),
+				retry,
+				?delay,
+				at_hash = ?self.at.hash,
+				"view::submit_many_with_retry: retrying transient failures"
+			);
+			self.metrics
+				.report(|metrics| metrics.view_submit_retries.inc_by(to_retry.len() as _));
+			futures_timer::Delay::new(delay).await;
+
+			let retried = self.submit_many(to_retry.iter().map(|index| xts[*index].clone())).await;
+			for (index, result) in to_retry.into_iter().zip(retried) {
+				results[index] = result;
+			}
+		}
+		results
+	}
+
+	/// Classifies the submission error, returns the error together with the flag indicating if
+	/// the failure is transient and the submission may succeed if retried.
+	///
+	/// Errors not originating from the pool (e.g. the state temporarily unavailable), unknown
+	/// validity and unknown block are considered transient.
+	fn is_retryable(error: ChainApi::Error) -> (ChainApi::Error, bool) {
+		match error.into_pool_error() {
+			Ok(error) => {
+				let retryable = matches!(
+					error,
+					TxPoolError::UnknownTransaction(_) | TxPoolError::InvalidBlockId(_)
+				);
+				(error.into(), retryable)
+			},
+			Err(error) => (error, true),
+		}
+	}
+
+	/// Imports many unvalidated extrinsics into the view, bypassing the size check and the
+	/// pre-validation filter.
+	///
+	/// The submission is instrumented with the `view::submit_many` span, carrying the hash of the
+	/// view's block and the number of submitted transactions.
+	async fn submit_many_unfiltered(
+		&self,
+		xts: impl IntoIterator<Item = (TimedTransactionSource, ExtrinsicFor<ChainApi>)>,
+	) -> Vec<Result<ValidatedPoolSubmitOutcome<ChainApi>, ChainApi::Error>> {
+		let start = Instant::now();
+		let xts = xts.into_iter().collect::<Vec<_>>();
 		if tracing::enabled!(target: LOG_TARGET, tracing::Level::TRACE) {
-			let xts = xts.into_iter().collect::<Vec<_>>();
 			log_xt_trace!(
 				target: LOG_TARGET,
 				xts.iter().map(|(_,xt)| self.pool.validated_pool().api().hash_and_length(xt).0),
 				"view::submit_many at:{}",
 				self.at.hash
 			);
-			self.pool.submit_at(&self.at, xts).await
-		} else {
-			self.pool.submit_at(&self.at, xts).await
 		}
+		let span = tracing::debug_span!(
+			target: LOG_TARGET,
+			"view::submit_many",
+			at_hash = ?self.at.hash,
+			count = xts.len(),
+		);
+		let results = self.pool.submit_at(&self.at, xts).instrument(span).await;
+
+		let duration = start.elapsed();
+		self.report_validation_counters();
+		self.metrics.report(|metrics| {
+			metrics.view_submit_many_duration.observe(duration.as_secs_f64());
+		});
+		let threshold = self.pool.validated_pool().options().slow_submit_many_threshold;
+		if threshold.is_some_and(|threshold| duration > threshold) {
+			warn!(
+				target: LOG_TARGET,
+				count = results.len(),
+				at_hash = ?self.at.hash,
+				?duration,
+				"view::submit_many: slow submission"
+			);
+		}
+		self.report_near_capacity();
+		self.refresh_status();
+		results
+	}
+
+	/// Reports the view whose ready or future queue is filled above the soft limit.
+	///
+	/// The `view_near_capacity` counter is increased, the warning is logged at most once per
+	/// [`NEAR_CAPACITY_WARN_INTERVAL`]. Refer to [`graph::Options::soft_limit_ratio`] for more
+	/// details.
+	fn report_near_capacity(&self) {
+		let validated_pool = self.pool.validated_pool();
+		let options = validated_pool.options();
+		let Some(ratio) = options.soft_limit_ratio else { return };
+
+		let status = validated_pool.status();
+		let above_soft_limit = |limit: &graph::base_pool::Limit, count: usize, bytes: usize| {
+			count as f64 > limit.count as f64 * ratio ||
+				bytes as f64 > limit.total_bytes as f64 * ratio
+		};
+		if !above_soft_limit(&options.ready, status.ready, status.ready_bytes) &&
+			!above_soft_limit(&options.future, status.future, status.future_bytes)
+		{
+			return
+		}
+
+		self.metrics.report(|metrics| metrics.view_near_capacity.inc());
+		let mut last_warning = self.last_near_capacity_warning.lock();
+		if last_warning.is_some_and(|instant| instant.elapsed() < NEAR_CAPACITY_WARN_INTERVAL) {
+			return
+		}
+		*last_warning = Some(Instant::now());
+		warn!(
+			target: LOG_TARGET,
+			ready = status.ready,
+			future = status.future,
+			ready_bytes = status.ready_bytes,
+			future_bytes = status.future_bytes,
+			ratio,
+			at_hash = ?self.at.hash,
+			"view::submit_many: view is near its capacity"
+		);
+	}
+
+	/// Imports many unvalidated extrinsics into the view, yielding the results as a stream.
+	///
+	/// Unlike [`Self::submit_many`] the results are not collected: transactions are validated and
+	/// submitted one by one (in the order given by `xts`) and every item of the stream contains
+	/// the index of transaction in `xts` and the result of its submission. This allows the caller
+	/// to process the results of large batches incrementally.
+	pub(super) fn submit_many_stream(
+		self: &Arc<Self>,
+		xts: impl IntoIterator<Item = (TimedTransactionSource, ExtrinsicFor<ChainApi>)>,
+	) -> impl Stream<Item = (usize, Result<ValidatedPoolSubmitOutcome<ChainApi>, ChainApi::Error>)>
+	{
+		let view = self.clone();
+		futures::stream::iter(xts.into_iter().enumerate()).then(move |(index, (source, xt))| {
+			let view = view.clone();
+			async move { (index, view.submit_one(source, xt).await) }
+		})
+	}
+
+	/// Imports many unvalidated extrinsics into the view, stopping at the first systemic error.
+	///
+	/// Transactions are validated and submitted one by one (in the order given by `xts`). The
+	/// transaction-level failures (e.g. invalid transaction) are returned at their position and
+	/// the submission continues. On the first systemic error (refer to [`Self::is_systemic`]) the
+	/// submission stops: the results gathered so far are returned together with the error, the
+	/// remaining transactions are not validated. Unlike [`Self::submit_many_atomic`], the
+	/// transactions already submitted within this call are kept in the view.
+	pub(super) async fn submit_many_fail_fast(
+		&self,
+		xts: impl IntoIterator<Item = (TimedTransactionSource, ExtrinsicFor<ChainApi>)>,
+	) -> (Vec<Result<ValidatedPoolSubmitOutcome<ChainApi>, ChainApi::Error>>, Option<ChainApi::Error>)
+	{
+		let mut results = Vec::new();
+		for (source, xt) in xts {
+			match self.submit_one(source, xt).await {
+				Err(error) => {
+					let (error, systemic) = Self::is_systemic(error);
+					if systemic {
+						debug!(
+							target: LOG_TARGET,
+							submitted = results.len(),
+							at_hash = ?self.at.hash,
+							%error,
+							"view::submit_many_fail_fast: stopped on systemic error"
+						);
+						return (results, Some(error))
+					}
+					results.push(Err(error));
+				},
+				outcome => results.push(outcome),
+			}
+		}
+		(results, None)
+	}
+
+	/// Classifies the submission error, returns the error together with the flag indicating if
+	/// the failure is systemic, i.e. not related to the submitted transaction.
+	///
+	/// Errors not originating from the pool (e.g. the state unavailable) and unknown block are
+	/// considered systemic.
+	fn is_systemic(error: ChainApi::Error) -> (ChainApi::Error, bool) {
+		match error.into_pool_error() {
+			Ok(error) => {
+				let systemic = matches!(error, TxPoolError::InvalidBlockId(_));
+				(error.into(), systemic)
+			},
+			Err(error) => (error, true),
+		}
+	}
+
+	/// Imports many unvalidated extrinsics into the view with the all-or-nothing semantics.
+	///
+	/// Transactions are validated and submitted one by one (in the order given by `xts`). On the
+	/// first failure, the transactions already submitted within this call are removed from the
+	/// view and the index of the failing transaction is returned together with the error.
+	///
+	/// On rollback the transactions submitted within this call are removed from the view (the
+	/// Dropped event is triggered for them), while the transactions depending on them which were
+	/// not submitted within this call are kept in the future queue.
+	pub(super) async fn submit_many_atomic(
+		&self,
+		xts: impl IntoIterator<Item = (TimedTransactionSource, ExtrinsicFor<ChainApi>)>,
+	) -> Result<Vec<ValidatedPoolSubmitOutcome<ChainApi>>, (usize, ChainApi::Error)> {
+		let mut outcomes = Vec::new();
+		for (index, (source, xt)) in xts.into_iter().enumerate() {
+			match self.submit_one(source, xt).await {
+				Ok(outcome) => outcomes.push(outcome),
+				Err(error) => {
+					self.rollback(outcomes.iter().map(|outcome| outcome.hash()).collect());
+					return Err((index, error))
+				},
+			}
+		}
+		Ok(outcomes)
+	}
+
+	/// Removes the given transactions from the view, keeping their dependents.
+	///
+	/// The ready dependents which were not rolled back are moved to the future queue, i.e. the
+	/// state they were in before the rolled back transactions were submitted. The Dropped event is
+	/// triggered for every rolled back transaction.
+	fn rollback(&self, hashes: HashSet<ExtrinsicHash<ChainApi>>) {
+		if hashes.is_empty() {
+			return
+		}
+
+		let validated_pool = self.pool.validated_pool();
+		let rolled_back = hashes
+			.iter()
+			.filter(|tx_hash| validated_pool.remove_single(tx_hash).is_some())
+			.collect::<Vec<_>>();
+		validated_pool.dispatch_events(|listener| {
+			rolled_back.iter().for_each(|tx_hash| listener.dropped(tx_hash))
+		});
+
+		debug!(
+			target: LOG_TARGET,
+			requested = hashes.len(),
+			rolled_back = rolled_back.len(),
+			at_hash = ?self.at.hash,
+			"view::rollback"
+		);
+
+		self.refresh_status();
+	}
+
+	/// Moves all the transactions from this view into the `target` view.
+	///
+	/// All the transactions (ready and future) are removed from this view and submitted to the
+	/// `target` view, with their original sources. Ready transactions are submitted first, in the
+	/// order of their dependencies, followed by the future transactions. No events are triggered
+	/// for the removed transactions.
+	///
+	/// Transactions are revalidated at the `target` view's block, so some of them may fail to
+	/// migrate (e.g. if they are invalid at that block). The returned vector contains the hash of
+	/// every drained transaction together with the result of its submission.
+	pub(super) async fn drain_into(
+		&self,
+		target: &Self,
+	) -> Vec<(ExtrinsicHash<ChainApi>, Result<ValidatedPoolSubmitOutcome<ChainApi>, ChainApi::Error>)>
+	{
+		let validated_pool = self.pool.validated_pool();
+		let (hashes, xts): (Vec<_>, Vec<_>) = {
+			let pool = validated_pool.pool.read();
+			pool.ready()
+				.map(|tx| (tx.hash, (tx.source.clone(), tx.data.clone())))
+				.chain(pool.futures().map(|tx| (tx.hash, (tx.source.clone(), tx.data.clone()))))
+				.unzip()
+		};
+
+		debug!(
+			target: LOG_TARGET,
+			count = hashes.len(),
+			at_hash = ?self.at.hash,
+			target_at_hash = ?target.at.hash,
+			"view::drain_into"
+		);
+
+		validated_pool.remove_subtree(&hashes, false, |_, _| {});
+		self.refresh_status();
+		hashes.into_iter().zip(target.submit_many(xts).await).collect()
 	}
 
 	/// Synchronously imports single unvalidated extrinsics into the view.
@@ -346,47 +1078,227 @@ where
 		&self,
 		xt: ExtrinsicFor<ChainApi>,
 	) -> Result<ValidatedPoolSubmitOutcome<ChainApi>, ChainApi::Error> {
+		let validated = self.validate_local(xt)?;
+		let result = self.pool.validated_pool().submit(vec![validated]).remove(0);
+		self.refresh_status();
+		result
+	}
+
+	/// Synchronously imports single unvalidated extrinsic into the view, returning its priority.
+	///
+	/// Works as [`Self::submit_local`], the priority assigned to the transaction by the runtime
+	/// validation is returned alongside the outcome, so the callers (e.g. RPC) do not need to
+	/// validate the transaction again to learn it.
+	pub(super) fn submit_local_with_priority(
+		&self,
+		xt: ExtrinsicFor<ChainApi>,
+	) -> Result<(ValidatedPoolSubmitOutcome<ChainApi>, TransactionPriority), ChainApi::Error> {
+		let outcome = self.submit_local(xt)?;
+		let priority = outcome
+			.priority()
+			.expect("Priority is known for every submitted valid transaction. qed");
+		Ok((outcome, priority))
+	}
+
+	/// Synchronously imports many unvalidated local extrinsics into the view.
+	///
+	/// Every transaction is validated as in [`Self::submit_local`], then all the valid ones are
+	/// submitted together, so the inner pool lock is acquired once for the whole batch. The
+	/// results are returned in the order given by `xts`.
+	pub(super) fn submit_local_many(
+		&self,
+		xts: Vec<ExtrinsicFor<ChainApi>>,
+	) -> Vec<Result<ValidatedPoolSubmitOutcome<ChainApi>, ChainApi::Error>> {
+		let mut failures = Vec::with_capacity(xts.len());
+		let validated = xts
+			.into_iter()
+			.filter_map(|xt| match self.validate_local(xt) {
+				Ok(validated) => {
+					failures.push(None);
+					Some(validated)
+				},
+				Err(error) => {
+					failures.push(Some(error));
+					None
+				},
+			})
+			.collect::<Vec<_>>();
+
+		let mut results = self.pool.validated_pool().submit(validated).into_iter();
+		self.refresh_status();
+		failures
+			.into_iter()
+			.map(|failure| match failure {
+				Some(error) => Err(error),
+				None => results.next().expect("Result exists for every validated transaction. qed"),
+			})
+			.collect()
+	}
+
+	/// Synchronously validates the local extrinsic at the view's validation block.
+	fn validate_local(
+		&self,
+		xt: ExtrinsicFor<ChainApi>,
+	) -> Result<ValidatedTransactionFor<ChainApi>, ChainApi::Error> {
+		self.pre_validate(&xt)?;
 		let (tx_hash, length) = self.pool.validated_pool().api().hash_and_length(&xt);
+		let at = self.validation_block();
 		trace!(
 			target: LOG_TARGET,
 			?tx_hash,
 			view_at_hash = ?self.at.hash,
+			validation_at_hash = ?at.hash,
 			"view::submit_local"
 		);
-		let validity = self
-			.pool
-			.validated_pool()
-			.api()
-			.validate_transaction_blocking(
-				self.at.hash,
-				sc_transaction_pool_api::TransactionSource::Local,
-				Arc::from(xt.clone()),
-			)?
-			.map_err(|e| {
-				match e {
-					TransactionValidityError::Invalid(i) => TxPoolError::InvalidTransaction(i),
-					TransactionValidityError::Unknown(u) => TxPoolError::UnknownTransaction(u),
-				}
-				.into()
-			})?;
+		let started = self.validation_trace_start();
+		let validity = self.pool.validated_pool().validate_transaction_blocking(
+			tx_hash,
+			at.hash,
+			sc_transaction_pool_api::TransactionSource::Local,
+			Arc::from(xt.clone()),
+		);
+		self.trace_validation(tx_hash, started);
+		self.report_validation_counters();
+		let validity = validity?.map_err(|e| {
+			match e {
+				TransactionValidityError::Invalid(i) => TxPoolError::InvalidTransaction(i),
+				TransactionValidityError::Unknown(u) => TxPoolError::UnknownTransaction(u),
+			}
+			.into()
+		})?;
 
 		let block_number = self
 			.pool
 			.validated_pool()
 			.api()
-			.block_id_to_number(&BlockId::hash(self.at.hash))?
-			.ok_or_else(|| TxPoolError::InvalidBlockId(format!("{:?}", self.at.hash)))?;
+			.block_id_to_number(&BlockId::hash(at.hash))?
+			.ok_or_else(|| TxPoolError::InvalidBlockId(format!("{:?}", at.hash)))?;
 
-		let validated = ValidatedTransaction::valid_at(
+		Ok(ValidatedTransaction::valid_at(
 			block_number.saturated_into::<u64>(),
 			tx_hash,
 			TimedTransactionSource::new_local(true),
 			Arc::from(xt),
 			length,
 			validity,
+		))
+	}
+
+	/// Checks the transaction against the pre-validation filter of the view (if any).
+	///
+	/// All the transactions are rejected with [`TxPoolError::ImmediatelyDropped`] if the view is
+	/// frozen.
+	fn pre_validate(&self, xt: &ExtrinsicFor<ChainApi>) -> Result<(), ChainApi::Error> {
+		if self.is_frozen() {
+			trace!(
+				target: LOG_TARGET,
+				tx_hash = ?self.pool.validated_pool().api().hash_and_length(xt).0,
+				at_hash = ?self.at.hash,
+				"view::pre_validate: view is frozen"
+			);
+			return Err(TxPoolError::ImmediatelyDropped.into())
+		}
+		let Some(filter) = self.pre_validation_filter.as_ref() else { return Ok(()) };
+		filter(xt).map_err(|error| {
+			trace!(
+				target: LOG_TARGET,
+				tx_hash = ?self.pool.validated_pool().api().hash_and_length(xt).0,
+				at_hash = ?self.at.hash,
+				?error,
+				"view::pre_validate: transaction rejected"
+			);
+			self.metrics.report(|metrics| metrics.view_pre_validation_filtered.inc());
+			error.into()
+		})
+	}
+
+	/// Rejects the transaction which encoded length exceeds the given limit.
+	fn check_size(&self, xt: &ExtrinsicFor<ChainApi>, limit: usize) -> Result<(), ChainApi::Error> {
+		let (tx_hash, size) = self.pool.validated_pool().api().hash_and_length(xt);
+		if size <= limit {
+			return Ok(())
+		}
+		trace!(
+			target: LOG_TARGET,
+			?tx_hash,
+			size,
+			limit,
+			at_hash = ?self.at.hash,
+			"view::check_size: transaction too large"
 		);
+		self.metrics.report(|metrics| metrics.view_submit_oversized_rejected.inc());
+		Err(TxPoolError::TooLarge { size, limit }.into())
+	}
+
+	/// Returns the block at which the transactions of the view shall be validated.
+	///
+	/// This is the view's block, unless [`graph::Options::validate_at_best`] is set and the best
+	/// block can be determined.
+	fn validation_block(&self) -> HashAndNumber<ChainApi::Block> {
+		let validated_pool = self.pool.validated_pool();
+		if !validated_pool.options().validate_at_best {
+			return self.at.clone()
+		}
+
+		let api = validated_pool.api();
+		let best = api.best_block_hash().and_then(|best| {
+			best.map(|hash| {
+				api.resolve_block_number(hash).map(|number| HashAndNumber { hash, number })
+			})
+			.transpose()
+		});
+
+		match best {
+			Ok(Some(best)) => best,
+			Ok(None) => self.at.clone(),
+			Err(error) => {
+				debug!(
+					target: LOG_TARGET,
+					at_hash = ?self.at.hash,
+					?error,
+					"view::validation_block: cannot determine best block, using view's block"
+				);
+				self.at.clone()
+			},
+		}
+	}
+
+	/// Returns `true` if the view shall not be used as an origin for the new views.
+	///
+	/// The flag is set when the revalidation of the view was aborted after
+	/// [`graph::Options::max_consecutive_invalid`] consecutive invalid transactions (e.g. due to
+	/// runtime upgrade). The new views shall be built from scratch instead of cloning this one.
+	pub(super) fn requires_rebuild(&self) -> bool {
+		self.requires_rebuild.load(Ordering::Relaxed)
+	}
+
+	/// Returns the instant at which the most recent background revalidation of the view was
+	/// completed (i.e. its result was applied), or `None` if the view was never revalidated.
+	///
+	/// Allows to decide which views shall be revalidated next.
+	pub(super) fn last_revalidation_instant(&self) -> Option<Instant> {
+		*self.last_revalidation.lock()
+	}
+
+	/// Returns `true` if the background revalidation of the view is currently in progress.
+	///
+	/// The revalidation is considered to be in progress if it was started and the revalidation
+	/// worker has not completed (or was not requested to complete) it yet.
+	pub(super) fn revalidation_in_progress(&self) -> bool {
+		self.revalidation_worker_channels
+			.lock()
+			.as_ref()
+			.is_some_and(|channels| channels.finish_revalidation_request_tx.is_some())
+	}
 
-		self.pool.validated_pool().submit(vec![validated]).remove(0)
+	/// Returns the hash of the block the view is associated with.
+	pub(super) fn hash(&self) -> BlockHash<ChainApi> {
+		self.at.hash
+	}
+
+	/// Returns the number of the block the view is associated with.
+	pub(super) fn number(&self) -> NumberFor<ChainApi> {
+		self.at.number
 	}
 
 	/// Status of the pool associated with the view.
@@ -394,6 +1306,309 @@ where
 		self.pool.validated_pool().status()
 	}
 
+	/// Returns the total number of validated (ready and future) transactions in the view.
+	///
+	/// Cheaper than [`Self::status`]: the inner pool lock is acquired once and the sizes of the
+	/// queues are not gathered.
+	pub(super) fn validated_count(&self) -> usize {
+		self.pool.validated_pool().pool.read().transactions_count()
+	}
+
+	/// Returns the receiver observing the status of the view.
+	///
+	/// The status is updated at the end of every operation changing the content of the view
+	/// (submission, pruning, applying the revalidation results, removal). Updates are coalesced
+	/// (watch channel semantics): only the most recent status is kept, so rapid changes may be
+	/// observed as a single transition, and the operations not changing the status do not notify
+	/// the receivers. The status of the cloned view is not observed by the receivers of the
+	/// original view.
+	pub(super) fn subscribe_status(&self) -> tokio::sync::watch::Receiver<PoolStatus> {
+		self.status_sink.subscribe()
+	}
+
+	/// Publishes the current status of the view to the receivers of [`Self::subscribe_status`],
+	/// if it differs from the last published one.
+	pub(super) fn refresh_status(&self) {
+		let status = self.status();
+		self.status_sink.send_if_modified(|current| {
+			let modified = current.ready != status.ready ||
+				current.ready_bytes != status.ready_bytes ||
+				current.future != status.future ||
+				current.future_bytes != status.future_bytes;
+			if modified {
+				*current = status;
+			}
+			modified
+		});
+	}
+
+	/// Returns the statistics of the view.
+	///
+	/// Cheaper than querying the individual figures separately, as the internal pool lock is
+	/// acquired only once.
+	pub(super) fn stats_snapshot(&self) -> ViewStats {
+		let revalidation_in_progress = self.revalidation_in_progress();
+		let now = Instant::now();
+
+		let pool = self.pool.validated_pool().pool.read();
+		let status = pool.status();
+		let oldest_timestamp = pool
+			.ready()
+			.filter_map(|tx| tx.source.timestamp)
+			.chain(pool.futures().filter_map(|tx| tx.source.timestamp))
+			.min();
+
+		ViewStats {
+			ready: status.ready,
+			future: status.future,
+			total_bytes: status.ready_bytes + status.future_bytes,
+			oldest_tx_age: oldest_timestamp.map(|t| now.saturating_duration_since(t)),
+			revalidation_in_progress,
+			quarantined: self.quarantine.lock().len(),
+			clone_depth: self.clone_depth,
+		}
+	}
+
+	/// Returns the ages of all (ready and future) transactions in the view.
+	///
+	/// The age is computed from the transaction's [`TimedTransactionSource`] timestamp,
+	/// transactions without the timestamp are skipped.
+	pub(super) fn transactions_ages(&self) -> Vec<Duration> {
+		let now = Instant::now();
+		let pool = self.pool.validated_pool().pool.read();
+		pool.ready()
+			.filter_map(|tx| tx.source.timestamp)
+			.chain(pool.futures().filter_map(|tx| tx.source.timestamp))
+			.map(|timestamp| now.saturating_duration_since(timestamp))
+			.collect()
+	}
+
+	/// Reports the ages of the transactions remaining in the view to the metrics.
+	///
+	/// Intended to be called when the view is removed on finalization. Allows to observe how long
+	/// the transactions waited in the view before the forks were resolved.
+	pub(super) fn report_final_ages(&self) {
+		self.metrics.report(|metrics| {
+			self.transactions_ages()
+				.into_iter()
+				.for_each(|age| metrics.view_tx_final_age.observe(age.as_secs_f64()))
+		});
+	}
+
+	/// Shrinks the capacity of the collections of the view's internal pool as much as possible.
+	///
+	/// Intended to be called after a large number of transactions was removed from the view
+	/// (e.g. after processing a reorg), as the collections retain their capacity otherwise.
+	/// Returns the estimated number of reclaimed bytes.
+	pub(super) fn shrink_to_fit(&self) -> usize {
+		let reclaimed = self.pool.validated_pool().pool.write().shrink_to_fit();
+		self.metrics.report(|metrics| {
+			metrics.view_shrink_reclaimed_bytes.inc_by(reclaimed as _);
+		});
+		debug!(target: LOG_TARGET, reclaimed, at_hash = ?self.at.hash, "view::shrink_to_fit");
+		reclaimed
+	}
+
+	/// Prepares the view to be dropped once the given block is finalized.
+	///
+	/// Does nothing if `finalized` is below the block of the view. Otherwise the in-flight
+	/// background revalidation (if any) is cancelled and its results are discarded, the view is
+	/// frozen (i.e. all subsequent submissions are rejected) and the aggregated revalidation
+	/// metrics (if any) are flushed. Intended to be called by the pool for the views removed on
+	/// finalization. The capacity of the internal collections is not shrunk, as the memory is
+	/// released once the view is dropped.
+	pub(super) fn on_finalized(&self, finalized: &HashAndNumber<ChainApi::Block>) {
+		if finalized.number < self.at.number {
+			return
+		}
+		debug!(
+			target: LOG_TARGET,
+			at_hash = ?self.at.hash,
+			finalized_hash = ?finalized.hash,
+			"view::on_finalized"
+		);
+		self.frozen.store(true, Ordering::Relaxed);
+		// Dropping the channels terminates the worker, its result is not applied.
+		self.revalidation_worker_channels.lock().take();
+		self.revalidation_metrics.flush();
+	}
+
+	/// Returns true if the view does not accept new transactions.
+	///
+	/// Refer to [`Self::on_finalized`] for more details.
+	pub(super) fn is_frozen(&self) -> bool {
+		self.frozen.load(Ordering::Relaxed)
+	}
+
+	/// Returns the hashes of ready transactions in the view, in no particular order.
+	///
+	/// Cheaper than iterating over the ready transactions, as neither the transactions nor the
+	/// internal ready set are cloned.
+	pub(super) fn ready_hashes(&self) -> Vec<ExtrinsicHash<ChainApi>> {
+		self.pool.validated_pool().pool.read().ready_hashes()
+	}
+
+	/// Returns the hashes of ready transactions submitted at or after the given instant.
+	///
+	/// The submission time is taken from the transaction's [`TimedTransactionSource`] timestamp,
+	/// transactions without the timestamp are skipped. Hashes are ordered by the timestamp
+	/// (ascending). Allows to poll the view for transactions that became ready since the last
+	/// poll.
+	pub(super) fn ready_since(&self, since: Instant) -> Vec<ExtrinsicHash<ChainApi>> {
+		let mut ready = self
+			.pool
+			.validated_pool()
+			.ready()
+			.filter_map(|tx| {
+				tx.source
+					.timestamp
+					.filter(|timestamp| *timestamp >= since)
+					.map(|t| (t, tx.hash))
+			})
+			.collect::<Vec<_>>();
+		ready.sort_by_key(|(timestamp, _)| *timestamp);
+		ready.into_iter().map(|(_, hash)| hash).collect()
+	}
+
+	/// Returns at most `n` ready transactions of the view, in the order they would be included
+	/// into the block (i.e. by priority, respecting the dependencies).
+	///
+	/// The ready queue is walked with the [`graph::ReadyCursor`], which stops as soon as `n`
+	/// transactions are collected, without taking the snapshot of the whole queue.
+	pub(super) fn ready_iter_limited(&self, n: usize) -> Vec<TransactionFor<ChainApi>> {
+		let pool = self.pool.validated_pool().pool.read();
+		let mut cursor = graph::ReadyCursor::default();
+		std::iter::from_fn(|| pool.ready_next(&mut cursor)).take(n).collect()
+	}
+
+	/// Returns the bodies of the ready transactions of the view, in the order they would be
+	/// included into the block (i.e. by priority, respecting the dependencies).
+	///
+	/// Intended as the primitive for the `author_pendingExtrinsics` RPC.
+	pub(super) fn pending_extrinsics(&self) -> Vec<ExtrinsicFor<ChainApi>> {
+		self.pool.validated_pool().ready().map(|tx| tx.data.clone()).collect()
+	}
+
+	/// Returns an iterator over the ready transactions of the view together with their sources.
+	///
+	/// Transactions are yielded in the order they would be included into the block. The source is
+	/// the [`TimedTransactionSource`] stored with the transaction, allowing e.g. block builders to
+	/// treat local and external transactions differently.
+	pub(super) fn ready_with_sources(
+		&self,
+	) -> impl Iterator<Item = (TransactionFor<ChainApi>, TimedTransactionSource)> {
+		self.pool.validated_pool().ready().map(|tx| {
+			let source = tx.source.clone();
+			(tx, source)
+		})
+	}
+
+	/// Searches the view for the transaction sent by `sender` with the given `nonce`.
+	///
+	/// The sender and nonce of the transaction are provided by the runtime-specific `extract`
+	/// closure. Both ready and future transactions are searched. Intended as a lookup primitive
+	/// for the transaction replacement (e.g. fee-bumping).
+	pub(super) fn find_by_sender_nonce<AccountId: PartialEq, Nonce: PartialEq>(
+		&self,
+		extract: impl Fn(&ExtrinsicFor<ChainApi>) -> Option<(AccountId, Nonce)>,
+		sender: &AccountId,
+		nonce: Nonce,
+	) -> Option<ExtrinsicHash<ChainApi>> {
+		let matches = |xt: &ExtrinsicFor<ChainApi>| {
+			extract(xt)
+				.is_some_and(|(tx_sender, tx_nonce)| tx_sender == *sender && tx_nonce == nonce)
+		};
+
+		let pool = self.pool.validated_pool().pool.read();
+		let mut ready = pool.ready();
+		ready
+			.find(|tx| matches(&tx.data))
+			.map(|tx| tx.hash)
+			.or_else(|| pool.futures().find(|tx| matches(&tx.data)).map(|tx| tx.hash))
+	}
+
+	/// Estimates how many ready transactions of the view fit into the block of given `max_weight`.
+	///
+	/// The ready transactions are taken greedily, in the order they would be included into the
+	/// block (i.e. by priority, respecting the dependencies). The weight of the transaction is
+	/// provided by the runtime-specific `weight_of` closure. A transaction exceeding the remaining
+	/// weight is skipped together with the transactions depending on it. This is a read-only
+	/// planning aid, nothing is removed from the view.
+	///
+	/// Returns the number of transactions that fit and their total weight.
+	pub(super) fn estimate_block_fill(
+		&self,
+		max_weight: Weight,
+		weight_of: impl Fn(&ExtrinsicFor<ChainApi>) -> Weight,
+	) -> (usize, Weight) {
+		let mut ready = self.pool.validated_pool().ready();
+		let (mut count, mut total_weight) = (0, Weight::zero());
+		while let Some(tx) = ready.next() {
+			let weight = total_weight.saturating_add(weight_of(&tx.data));
+			if weight.all_lte(max_weight) {
+				count += 1;
+				total_weight = weight;
+			} else {
+				ready.report_invalid(&tx);
+			}
+		}
+		(count, total_weight)
+	}
+
+	/// Returns an iterator over the transactions in the future queue of the view.
+	///
+	/// Every item contains the hash of transaction, the transaction itself and the tags that the
+	/// transaction is still waiting for (i.e. required tags which are not provided by any ready
+	/// transaction). Intended for debugging transactions that are not progressing.
+	///
+	/// The iterator operates on the snapshot of the future queue taken when this method is called.
+	pub(super) fn iter_future(
+		&self,
+	) -> impl Iterator<Item = (ExtrinsicHash<ChainApi>, TransactionFor<ChainApi>, Vec<TransactionTag>)>
+	{
+		self.pool
+			.validated_pool()
+			.pool
+			.read()
+			.futures_waiting()
+			.map(|waiting| {
+				(
+					waiting.transaction.hash,
+					waiting.transaction.clone(),
+					waiting.missing_tags.iter().cloned().collect(),
+				)
+			})
+			.collect::<Vec<_>>()
+			.into_iter()
+	}
+
+	/// Returns the tags required by the future transactions which are not provided by any ready
+	/// transaction.
+	///
+	/// Answers the question what the view is waiting for. Tags are sorted and deduplicated.
+	pub(super) fn missing_tags(&self) -> Vec<TransactionTag> {
+		let mut missing_tags = self
+			.pool
+			.validated_pool()
+			.pool
+			.read()
+			.futures_waiting()
+			.flat_map(|waiting| waiting.missing_tags.iter().cloned())
+			.collect::<Vec<_>>();
+		missing_tags.sort();
+		missing_tags.dedup();
+		missing_tags
+	}
+
+	/// Returns the snapshot of the provides index of the ready transactions.
+	///
+	/// Maps every tag provided by the ready transactions to the hash of the providing transaction.
+	/// Together with [`Self::missing_tags`] explains why the future transactions are (or are not)
+	/// promotable.
+	pub(super) fn provides_index(&self) -> HashMap<TransactionTag, ExtrinsicHash<ChainApi>> {
+		self.pool.validated_pool().pool.read().ready_provided_tags().clone()
+	}
+
 	/// Revalidates some part of transaction from the internal pool.
 	///
 	/// Intended to be called from the revalidation worker. The revalidation process can be
@@ -401,16 +1616,25 @@ where
 	/// `finish_revalidation_worker_channels`. Revalidation results are sent back over the `tx`
 	/// channels and shall be applied in maintain thread.
 	///
+	/// The revalidation is also terminated once the `shutdown` signal (shared by all the views) is
+	/// triggered.
+	///
 	/// View revalidation currently is not throttled, and until not terminated it will revalidate
 	/// all the transactions. Note: this can be improved if CPU usage due to revalidation becomes a
 	/// problem.
+	///
+	/// Ready transactions are pulled lazily, one at a time, using the [`graph::ReadyCursor`], so no
+	/// snapshot of the ready queue is taken: transactions removed in the meantime are not
+	/// revalidated, and transactions imported later may be skipped.
 	pub(super) async fn revalidate(
 		&self,
 		finish_revalidation_worker_channels: FinishRevalidationWorkerChannels<ChainApi>,
+		shutdown: &RevalidationShutdownSignal,
 	) {
 		let FinishRevalidationWorkerChannels {
 			mut finish_revalidation_request_rx,
 			revalidation_result_tx,
+			requested_at,
 		} = finish_revalidation_worker_channels;
 
 		trace!(
@@ -419,22 +1643,58 @@ where
 			"view::revalidate: at starting"
 		);
 		let start = Instant::now();
+		let queue_latency = start.saturating_duration_since(requested_at);
+		self.metrics.report(|metrics| {
+			metrics.view_revalidation_queue_latency.observe(queue_latency.as_secs_f64());
+		});
+
+		if self.validated_count() == 0 {
+			trace!(
+				target: LOG_TARGET,
+				at_hash = ?self.at.hash,
+				"view::revalidate: view is empty, nothing to revalidate"
+			);
+			self.revalidation_worker_channels.lock().as_mut().map(|ch| ch.remove_sender());
+			self.metrics.report(|metrics| {
+				metrics.view_revalidation_duration.observe(start.elapsed().as_secs_f64());
+			});
+			let empty_result = RevalidationResult {
+				revalidated: Default::default(),
+				invalid_hashes: Default::default(),
+				unknown_hashes: Default::default(),
+				removed_by_policy: 0,
+				quarantine: None,
+			};
+			let _ = revalidation_result_tx.send(empty_result).await;
+			return
+		}
+
 		let validated_pool = self.pool.validated_pool();
-		let api = validated_pool.api();
+		let at = self.validation_block();
 
-		let batch: Vec<_> = validated_pool.ready().collect();
-		let batch_len = batch.len();
+		let batch_len = validated_pool.pool.read().status().ready;
+		let mut batch_cursor = graph::ReadyCursor::default();
 
 		//todo: sort batch by revalidation timestamp | maybe not needed at all? xts will be getting
 		//out of the view...
 		//todo: revalidate future, remove if invalid [#5496]
 
-		let mut invalid_hashes = Vec::new();
-		let mut revalidated = IndexMap::new();
+		let max_consecutive_invalid = validated_pool.options().max_consecutive_invalid;
+		let mut consecutive_invalid = 0;
+		let mut circuit_break = false;
+		let mut expired: u64 = 0;
 
 		let mut validation_results = vec![];
-		let mut batch_iter = batch.into_iter();
 		loop {
+			if shutdown.is_triggered() {
+				trace!(
+					target: LOG_TARGET,
+					at_hash = ?self.at.hash,
+					"view::revalidate: shutdown signal already triggered"
+				);
+				break
+			}
+
 			let mut should_break = false;
 			tokio::select! {
 				_ = finish_revalidation_request_rx.recv() => {
@@ -445,10 +1705,37 @@ where
 					);
 					break
 				}
+				_ = shutdown.triggered() => {
+					trace!(
+						target: LOG_TARGET,
+						at_hash = ?self.at.hash,
+						"view::revalidate: shutdown signal received"
+					);
+					break
+				}
 				_ = async {
-					if let Some(tx) = batch_iter.next() {
-						let validation_result = (api.validate_transaction(self.at.hash, tx.source.clone().into(), tx.data.clone()).await, tx.hash, tx);
+					let next = validated_pool.pool.read().ready_next(&mut batch_cursor);
+					if let Some(tx) = next {
+						if Self::is_expired(&tx, &at) {
+							expired += 1;
+							let stale = Ok(Err(InvalidTransaction::Stale.into()));
+							validation_results.push((stale, tx.hash, tx));
+							return
+						}
+						let started = self.validation_trace_start();
+						let validation_result = (validated_pool.validate_transaction(tx.hash, at.hash, tx.source.clone().into(), tx.data.clone()).await, tx.hash, tx);
+						self.trace_validation(validation_result.1, started);
+						if matches!(validation_result.0, Ok(Err(TransactionValidityError::Invalid(_)))) {
+							consecutive_invalid += 1;
+						} else {
+							consecutive_invalid = 0;
+						}
 						validation_results.push(validation_result);
+						if max_consecutive_invalid.is_some_and(|max| consecutive_invalid >= max) {
+							self.revalidation_worker_channels.lock().as_mut().map(|ch| ch.remove_sender());
+							circuit_break = true;
+							should_break = true;
+						}
 					} else {
 						self.revalidation_worker_channels.lock().as_mut().map(|ch| ch.remove_sender());
 						should_break = true;
@@ -461,14 +1748,29 @@ where
 			}
 		}
 
+		if circuit_break {
+			debug!(
+				target: LOG_TARGET,
+				at_hash = ?self.at.hash,
+				consecutive_invalid,
+				"view::revalidate: too many consecutive invalid transactions, view requires rebuild"
+			);
+			self.requires_rebuild.store(true, Ordering::Relaxed);
+			self.metrics.report(|metrics| metrics.view_revalidation_circuit_break.inc());
+		}
+
 		let revalidation_duration = start.elapsed();
+		self.report_validation_counters();
 		self.metrics.report(|metrics| {
 			metrics.view_revalidation_duration.observe(revalidation_duration.as_secs_f64());
 		});
+		self.revalidation_metrics
+			.report(RevalidationCounters { expired_txs: expired, ..Default::default() });
 		debug!(
 			target: LOG_TARGET,
 			at_hash = ?self.at.hash,
 			count = validation_results.len(),
+			expired,
 			batch_len,
 			duration = ?revalidation_duration,
 			"view::revalidate"
@@ -479,24 +1781,103 @@ where
 			validation_results.iter().map(|x| (x.1, &x.0)),
 			"view::revalidate result: {:?}"
 		);
+		let revalidation_result = self.process_validation_results(&at, validation_results);
+
+		trace!(
+			target: LOG_TARGET,
+			at_hash = ?self.at.hash,
+			"view::revalidate: sending revalidation result"
+		);
+		if let Err(error) = revalidation_result_tx.send(revalidation_result).await {
+			trace!(
+				target: LOG_TARGET,
+				at_hash = ?self.at.hash,
+				?error,
+				"view::revalidate: sending revalidation_result failed"
+			);
+		}
+	}
+
+	/// Returns true if the validity window of the transaction elapsed before the given block, at
+	/// which the transaction would be validated (refer to [`Self::validation_block`]).
+	///
+	/// Such transaction would be rejected by the runtime anyway, so it is removed by the
+	/// revalidation as stale without calling the runtime.
+	fn is_expired(tx: &TransactionFor<ChainApi>, at: &HashAndNumber<ChainApi::Block>) -> bool {
+		tx.valid_till < at.number.saturated_into::<u64>()
+	}
+
+	/// Turns the results of the runtime validation of view's transactions into the
+	/// [`RevalidationResult`].
+	///
+	/// The revalidation policies (e.g. [`graph::Options::keep_unknown_local_transactions`]) are
+	/// applied here, the results are not yet applied to the internal pool. The updated quarantine
+	/// of the view is carried by the result and set when the result is applied.
+	fn process_validation_results(
+		&self,
+		at: &HashAndNumber<ChainApi::Block>,
+		validation_results: Vec<(
+			Result<TransactionValidity, ChainApi::Error>,
+			ExtrinsicHash<ChainApi>,
+			TransactionFor<ChainApi>,
+		)>,
+	) -> RevalidationResult<ChainApi> {
+		let validated_pool = self.pool.validated_pool();
+		let api = validated_pool.api();
+		let mut invalid_hashes = Vec::new();
+		let mut unknown_hashes = Vec::new();
+		let mut revalidated = IndexMap::new();
+		let mut removed_by_policy: u64 = 0;
+		let keep_unknown_local_transactions =
+			validated_pool.options().keep_unknown_local_transactions;
+		let quarantine_max_retries = validated_pool.options().unknown_quarantine_max_retries;
+
+		let mut quarantine = self.quarantine.lock().clone();
 		for (validation_result, tx_hash, tx) in validation_results {
+			// the transaction leaves the quarantine, unless it is quarantined again below
+			let retries = quarantine.remove(&tx_hash).unwrap_or_default();
 			match validation_result {
 				Ok(Err(TransactionValidityError::Invalid(_))) => {
 					invalid_hashes.push(tx_hash);
 				},
 				Ok(Ok(validity)) => {
+					// note: the timestamp is refreshed, so the age of transaction reflects the time
+					// of the most recent successful validation. The kind of source is retained.
 					revalidated.insert(
 						tx_hash,
 						ValidatedTransaction::valid_at(
-							self.at.number.saturated_into::<u64>(),
+							at.number.saturated_into::<u64>(),
 							tx_hash,
-							tx.source.clone(),
+							TimedTransactionSource::from_transaction_source(tx.source.source, true),
 							tx.data.clone(),
 							api.hash_and_length(&tx.data).1,
 							validity,
 						),
 					);
 				},
+				Ok(Err(TransactionValidityError::Unknown(error)))
+					if keep_unknown_local_transactions &&
+						tx.source.source == TransactionSource::Local =>
+				{
+					trace!(
+						target: LOG_TARGET,
+						?tx_hash,
+						?error,
+						"Keeping local transaction. Cannot determine transaction validity"
+					);
+				},
+				Ok(Err(TransactionValidityError::Unknown(error)))
+					if quarantine_max_retries.is_some_and(|max_retries| retries < max_retries) =>
+				{
+					quarantine.insert(tx_hash, retries + 1);
+					trace!(
+						target: LOG_TARGET,
+						?tx_hash,
+						?error,
+						retries,
+						"Quarantining. Cannot determine transaction validity"
+					);
+				},
 				Ok(Err(TransactionValidityError::Unknown(error))) => {
 					trace!(
 						target: LOG_TARGET,
@@ -504,7 +1885,10 @@ where
 						?error,
 						"Removing. Cannot determine transaction validity"
 					);
-					invalid_hashes.push(tx_hash);
+					if keep_unknown_local_transactions || quarantine_max_retries.is_some() {
+						removed_by_policy += 1;
+					}
+					unknown_hashes.push(tx_hash);
 				},
 				Err(error) => {
 					trace!(
@@ -513,33 +1897,66 @@ where
 						%error,
 						"Removing due to error during revalidation"
 					);
-					invalid_hashes.push(tx_hash);
+					unknown_hashes.push(tx_hash);
 				},
 			}
 		}
 
-		trace!(
+		RevalidationResult {
+			invalid_hashes,
+			unknown_hashes,
+			revalidated,
+			removed_by_policy,
+			quarantine: Some(quarantine),
+		}
+	}
+
+	/// Revalidates at most `budget` ready transactions of the view and applies the results.
+	///
+	/// Unlike the background revalidation, the transactions are validated inline (by the caller)
+	/// and the results are applied to the internal pool immediately. The transactions are taken
+	/// in the order they would be included into the block. Intended for the schedulers
+	/// distributing a global validation budget across the views, so it shall not be used while
+	/// the background revalidation of the view is in progress.
+	pub(super) async fn revalidate_with_budget(&self, budget: usize) -> RevalidationRun {
+		let validated_pool = self.pool.validated_pool();
+		let at = self.validation_block();
+
+		let batch = self.ready_iter_limited(budget);
+		let mut validation_results = Vec::with_capacity(batch.len());
+		for tx in batch {
+			let started = self.validation_trace_start();
+			let validation_result = validated_pool
+				.validate_transaction(tx.hash, at.hash, tx.source.clone().into(), tx.data.clone())
+				.await;
+			self.trace_validation(tx.hash, started);
+			validation_results.push((validation_result, tx.hash, tx));
+		}
+		self.report_validation_counters();
+
+		let validated = validation_results.len();
+		let revalidation_result = self.process_validation_results(&at, validation_results);
+		let invalid = revalidation_result.invalid_hashes.len();
+		self.apply_revalidation_result(revalidation_result);
+
+		debug!(
 			target: LOG_TARGET,
 			at_hash = ?self.at.hash,
-			"view::revalidate: sending revalidation result"
+			budget,
+			validated,
+			invalid,
+			"view::revalidate_with_budget"
 		);
-		if let Err(error) = revalidation_result_tx
-			.send(RevalidationResult { invalid_hashes, revalidated })
-			.await
-		{
-			trace!(
-				target: LOG_TARGET,
-				at_hash = ?self.at.hash,
-				?error,
-				"view::revalidate: sending revalidation_result failed"
-			);
-		}
+		RevalidationRun { validated, invalid, remaining_budget: budget - validated }
 	}
 
 	/// Sends revalidation request to the background worker.
 	///
 	/// Creates communication channels required to stop revalidation request and receive the
-	/// revalidation results and sends the revalidation request to the background worker.
+	/// revalidation results and sends the revalidation request to the background worker. The
+	/// capacities of the channels are given by
+	/// [`graph::Options::revalidation_finish_request_channel_capacity`] and
+	/// [`graph::Options::revalidation_result_channel_capacity`].
 	///
 	/// Intended to be called from maintain thread, at the very end of the maintain process.
 	///
@@ -555,9 +1972,11 @@ where
 			at_hash = ?view.at.hash,
 			"view::start_background_revalidation"
 		);
+		let options = view.pool.validated_pool().options();
 		let (finish_revalidation_request_tx, finish_revalidation_request_rx) =
-			tokio::sync::mpsc::channel(1);
-		let (revalidation_result_tx, revalidation_result_rx) = tokio::sync::mpsc::channel(1);
+			tokio::sync::mpsc::channel(options.revalidation_finish_request_channel_capacity.max(1));
+		let (revalidation_result_tx, revalidation_result_rx) =
+			tokio::sync::mpsc::channel(options.revalidation_result_channel_capacity.max(1));
 
 		let finish_revalidation_worker_channels = FinishRevalidationWorkerChannels::new(
 			finish_revalidation_request_rx,
@@ -582,6 +2001,10 @@ where
 	/// process, before the new view is cloned and updated. Applying results before cloning ensures
 	/// that view contains up-to-date set of revalidated transactions.
 	///
+	/// A transaction reported both as revalidated and as invalid (or unknown) is removed from the
+	/// view and not resubmitted, so it never reappears in the view after its removal. Such overlap
+	/// is not expected and is reported as a warning.
+	///
 	/// Refer to [*View revalidation*](../index.html#view-revalidation) for more details.
 	pub(super) async fn finish_revalidation(&self) {
 		trace!(
@@ -601,53 +2024,626 @@ where
 		} = revalidation_worker_channels;
 
 		if let Some(finish_revalidation_request_tx) = finish_revalidation_request_tx {
-			if let Err(error) = finish_revalidation_request_tx.send(()).await {
-				trace!(
+			tokio::select! {
+				result = finish_revalidation_request_tx.send(()) => {
+					if let Err(error) = result {
+						trace!(
+							target: LOG_TARGET,
+							at_hash = ?self.at.hash,
+							%error,
+							"view::finish_revalidation: sending cancellation request failed"
+						);
+					}
+				}
+				_ = futures_timer::Delay::new(FINISH_REVALIDATION_REQUEST_TIMEOUT) => {
+					warn!(
+						target: LOG_TARGET,
+						at_hash = ?self.at.hash,
+						timeout = ?FINISH_REVALIDATION_REQUEST_TIMEOUT,
+						"view::finish_revalidation: sending cancellation request timed out"
+					);
+				}
+			}
+		}
+
+		tokio::select! {
+			revalidation_result = revalidation_result_rx.recv() => {
+				if let Some(revalidation_result) = revalidation_result {
+					self.apply_revalidation_result(revalidation_result);
+				}
+			}
+			_ = futures_timer::Delay::new(FINISH_REVALIDATION_REQUEST_TIMEOUT) => {
+				warn!(
 					target: LOG_TARGET,
 					at_hash = ?self.at.hash,
-					%error,
-					"view::finish_revalidation: sending cancellation request failed"
+					timeout = ?FINISH_REVALIDATION_REQUEST_TIMEOUT,
+					"view::finish_revalidation: receiving revalidation result timed out"
 				);
 			}
 		}
+	}
 
-		if let Some(revalidation_result) = revalidation_result_rx.recv().await {
-			let start = Instant::now();
-			let revalidated_len = revalidation_result.revalidated.len();
-			let validated_pool = self.pool.validated_pool();
-			validated_pool.remove_invalid(&revalidation_result.invalid_hashes);
-			if revalidated_len > 0 {
-				self.pool.resubmit(revalidation_result.revalidated);
-			}
-
+	/// Applies the revalidation results to the internal pool.
+	///
+	/// Invalid and unknown transactions are removed from the view, the revalidated ones are
+	/// resubmitted.
+	fn apply_revalidation_result(&self, mut revalidation_result: RevalidationResult<ChainApi>) {
+		let start = Instant::now();
+		let overlapping = revalidation_result.discard_overlapping();
+		if overlapping > 0 {
+			warn!(
+				target: LOG_TARGET,
+				at_hash = ?self.at.hash,
+				overlapping,
+				"view::finish_revalidation: revalidated transactions also reported as removed"
+			);
 			self.metrics.report(|metrics| {
-				let _ = (
-					revalidation_result
-						.invalid_hashes
-						.len()
-						.try_into()
-						.map(|v| metrics.view_revalidation_invalid_txs.inc_by(v)),
-					revalidated_len
-						.try_into()
-						.map(|v| metrics.view_revalidation_resubmitted_txs.inc_by(v)),
-				);
+				metrics.view_revalidation_overlapping_txs.inc_by(overlapping as _)
 			});
+		}
+		let revalidated_len = revalidation_result.revalidated.len();
+		let validated_pool = self.pool.validated_pool();
+		let invalid = validated_pool.remove_invalid(&revalidation_result.invalid_hashes);
+		let unknown = validated_pool.remove_unknown(&revalidation_result.unknown_hashes);
+		self.notify_revalidation_removed(invalid.iter().chain(unknown.iter()).map(|tx| tx.hash));
+		if revalidated_len > 0 {
+			self.resubmit_revalidated(revalidation_result.revalidated);
+		}
+		self.refresh_status();
+		*self.last_revalidation.lock() = Some(Instant::now());
+		if let Some(mut quarantine) = revalidation_result.quarantine.take() {
+			// transactions removed from the view in the meantime leave the quarantine
+			quarantine.retain(|tx_hash, _| validated_pool.pool.read().is_imported(tx_hash));
+			let quarantined = quarantine.len();
+			*self.quarantine.lock() = quarantine;
+			self.metrics
+				.report(|metrics| metrics.view_quarantined_txs.set(quarantined as _));
+		}
+		self.metrics.report(|metrics| {
+			metrics
+				.view_revalidation_removed_by_policy_txs
+				.inc_by(revalidation_result.removed_by_policy)
+		});
+
+		self.revalidation_metrics.report(RevalidationCounters {
+			invalid_txs: revalidation_result.invalid_hashes.len() as _,
+			resubmitted_txs: revalidated_len as _,
+			..Default::default()
+		});
+
+		let duration = start.elapsed();
+		debug!(
+			name: REVALIDATION_APPLIED_EVENT,
+			target: LOG_TARGET,
+			at_hash = ?self.at.hash,
+			at_number = ?self.at.number,
+			invalid = revalidation_result.invalid_hashes.len(),
+			unknown = revalidation_result.unknown_hashes.len(),
+			revalidated = revalidated_len,
+			duration_secs = duration.as_secs_f64(),
+			?duration,
+			"view::finish_revalidation: applying revalidation result"
+		);
+	}
+
+	/// Returns a stream of hashes of the transactions removed from the view by the revalidation.
+	///
+	/// The hash is sent as soon as [`Self::finish_revalidation`] removes the transaction (found
+	/// invalid or of unknown validity, or depending on such transaction) from the view. Allows to
+	/// notify the transaction watchers without polling the view. The stream is not inherited by
+	/// the views cloned from this view.
+	pub(super) fn revalidation_removed_stream(
+		&self,
+	) -> impl Stream<Item = ExtrinsicHash<ChainApi>> {
+		let (sink, stream) =
+			tracing_unbounded("mpsc_txpool_revalidation_removed", VIEW_STREAM_WARN_THRESHOLD);
+		self.revalidation_removed_sinks.lock().push(sink);
+		stream
+	}
+
+	/// Sends the hashes of transactions removed by the revalidation to all alive streams.
+	fn notify_revalidation_removed(&self, hashes: impl Iterator<Item = ExtrinsicHash<ChainApi>>) {
+		let mut sinks = self.revalidation_removed_sinks.lock();
+		if sinks.is_empty() {
+			return
+		}
+		let hashes = hashes.collect::<Vec<_>>();
+		sinks.retain(|sink| hashes.iter().all(|hash| sink.unbounded_send(*hash).is_ok()));
+	}
+
+	/// Resubmits the revalidated transactions into the view.
+	///
+	/// Transactions are resubmitted in chunks of the size configured by
+	/// [`graph::Options::revalidation_resubmit_chunk_size`]. The pool lock is released between the
+	/// chunks.
+	///
+	/// The transactions which could not be reinstated (e.g. due to a conflict with a transaction
+	/// submitted in the meantime) are reported by the `view_revalidation_resubmit_failed` metric.
+	fn resubmit_revalidated(
+		&self,
+		mut revalidated: IndexMap<ExtrinsicHash<ChainApi>, ValidatedTransactionFor<ChainApi>>,
+	) {
+		let chunk_size = self
+			.pool
+			.validated_pool()
+			.options()
+			.revalidation_resubmit_chunk_size
+			.filter(|chunk_size| *chunk_size > 0)
+			.unwrap_or(usize::MAX);
+
+		let mut failed = Vec::new();
+		while !revalidated.is_empty() {
+			let rest = revalidated.split_off(chunk_size.min(revalidated.len()));
+			failed.extend(
+				self.pool
+					.resubmit(revalidated)
+					.into_iter()
+					.filter_map(|(tx_hash, outcome)| (!outcome.is_reinstated()).then_some(tx_hash)),
+			);
+			revalidated = rest;
+		}
 
+		if !failed.is_empty() {
 			debug!(
 				target: LOG_TARGET,
-				invalid = revalidation_result.invalid_hashes.len(),
-				revalidated = revalidated_len,
 				at_hash = ?self.at.hash,
-				duration = ?start.elapsed(),
-				"view::finish_revalidation: applying revalidation result"
+				?failed,
+				"view::resubmit_revalidated: transactions not reinstated"
 			);
+			self.revalidation_metrics.report(RevalidationCounters {
+				resubmit_failed: failed.len() as _,
+				..Default::default()
+			});
+		}
+	}
+
+	/// Speculatively validates the transaction at the child block of the view.
+	///
+	/// The transaction is validated (as the external one) at `at_child`, which is expected to be
+	/// the direct child of the view's block (e.g. the block being built by a collator). The valid
+	/// transaction is recorded in the staging set, which is separate from the ready and future
+	/// queues of the view: the live view is not affected until [`Self::commit_staged`] is called.
+	/// The staging set can be rolled back with [`Self::discard_staged`]. The staging set is not
+	/// inherited by the views cloned from this view.
+	///
+	/// Returns [`TxPoolError::InvalidBlockId`] if `at_child` is not the child of the view's block,
+	/// and [`TxPoolError::AlreadyImported`] if the transaction is already staged.
+	pub(super) async fn stage(
+		&self,
+		at_child: BlockHash<ChainApi>,
+		xt: ExtrinsicFor<ChainApi>,
+	) -> Result<StagedOutcome<ExtrinsicHash<ChainApi>>, ChainApi::Error> {
+		let api = self.pool.validated_pool().api();
+		let parent_hash = api.block_header(at_child)?.map(|header| *header.parent_hash());
+		if parent_hash != Some(self.at.hash) {
+			return Err(TxPoolError::InvalidBlockId(format!("{:?}", at_child)).into())
+		}
+
+		let tx_hash = api.hash_and_length(&xt).0;
+		if self.staged.lock().iter().any(|(hash, _)| *hash == tx_hash) {
+			return Err(TxPoolError::AlreadyImported(Box::new(tx_hash)).into())
+		}
+
+		let validity = api
+			.validate_transaction(at_child, TransactionSource::External, xt.clone())
+			.await?
+			.map_err(|e| {
+				match e {
+					TransactionValidityError::Invalid(i) => TxPoolError::InvalidTransaction(i),
+					TransactionValidityError::Unknown(u) => TxPoolError::UnknownTransaction(u),
+				}
+				.into()
+			})?;
+
+		let mut staged = self.staged.lock();
+		if staged.iter().any(|(hash, _)| *hash == tx_hash) {
+			return Err(TxPoolError::AlreadyImported(Box::new(tx_hash)).into())
+		}
+		staged.push((tx_hash, xt));
+		trace!(
+			target: LOG_TARGET,
+			?tx_hash,
+			at_hash = ?self.at.hash,
+			?at_child,
+			"view::stage"
+		);
+		Ok(StagedOutcome { hash: tx_hash, validity })
+	}
+
+	/// Imports the staged transactions into the view.
+	///
+	/// The staging set is cleared. As the validity at the child block may not hold at the view's
+	/// block, the staged transactions are submitted as in [`Self::submit_many`] (i.e. validated
+	/// at the view's block). The results are returned in the staging order.
+	pub(super) async fn commit_staged(
+		&self,
+	) -> Vec<Result<ValidatedPoolSubmitOutcome<ChainApi>, ChainApi::Error>> {
+		let staged = std::mem::take(&mut *self.staged.lock());
+		debug!(
+			target: LOG_TARGET,
+			at_hash = ?self.at.hash,
+			count = staged.len(),
+			"view::commit_staged"
+		);
+		let source =
+			TimedTransactionSource::from_transaction_source(TransactionSource::External, true);
+		self.submit_many(staged.into_iter().map(|(_, xt)| (source.clone(), xt))).await
+	}
+
+	/// Discards the staged transactions, returning their hashes.
+	///
+	/// The view is left unchanged. Refer to [`Self::stage`] for more details.
+	pub(super) fn discard_staged(&self) -> Vec<ExtrinsicHash<ChainApi>> {
+		let staged = std::mem::take(&mut *self.staged.lock());
+		debug!(
+			target: LOG_TARGET,
+			at_hash = ?self.at.hash,
+			count = staged.len(),
+			"view::discard_staged"
+		);
+		staged.into_iter().map(|(hash, _)| hash).collect()
+	}
+
+	/// Returns the validity of the transaction with given hash stored in the view.
+	///
+	/// The validity is rebuilt from the stored transaction, so the runtime is not called. As only
+	/// the block at which the transaction becomes invalid is stored, the returned `longevity` is
+	/// the remaining one, counted from the block of the view. Returns `None` if the transaction
+	/// is not in the view.
+	pub(super) fn get_validity(
+		&self,
+		tx_hash: &ExtrinsicHash<ChainApi>,
+	) -> Option<ValidTransaction> {
+		let tx = self.pool.validated_pool().by_hashes(&[*tx_hash]).pop().flatten()?;
+		Some(ValidTransaction {
+			priority: tx.priority,
+			requires: tx.requires.clone(),
+			provides: tx.provides.clone(),
+			longevity: tx.valid_till.saturating_sub(self.at.number.saturated_into::<u64>()),
+			propagate: tx.propagate,
+		})
+	}
+
+	/// Validates the transaction at the view's validation block with the given context.
+	///
+	/// The `recheck` flag distinguishes the initial validation from the re-validation, as the
+	/// runtime does. If `recheck` is `false`, the known validity is returned without calling into
+	/// the runtime: the validity stored in the view (refer to [`Self::get_validity`]) if the
+	/// transaction is in the view, or the validity cached for the given `source` (if the
+	/// validation cache is enabled). If `recheck` is `true` the runtime is always called and
+	/// neither the view nor the validation cache is updated with the result.
+	///
+	/// Intended for callers knowing that the re-validation of the transaction validated at an
+	/// ancestor block is not needed.
+	pub(super) async fn validate_transaction_with_context(
+		&self,
+		source: TransactionSource,
+		xt: ExtrinsicFor<ChainApi>,
+		recheck: bool,
+	) -> Result<TransactionValidity, ChainApi::Error> {
+		let validated_pool = self.pool.validated_pool();
+		let at = self.validation_block();
+		let tx_hash = validated_pool.api().hash_and_length(&xt).0;
+		trace!(
+			target: LOG_TARGET,
+			?tx_hash,
+			at_hash = ?at.hash,
+			?source,
+			recheck,
+			"view::validate_transaction_with_context"
+		);
+		if recheck {
+			return validated_pool.api().validate_transaction(at.hash, source, xt).await
+		}
+
+		if let Some(validity) = self.get_validity(&tx_hash) {
+			return Ok(Ok(validity))
 		}
+		let validity = validated_pool.validate_transaction(tx_hash, at.hash, source, xt).await;
+		self.report_validation_counters();
+		validity
+	}
+
+	/// For each hash, returns the transaction (either ready or future) if it is in the view.
+	pub(super) fn transactions_by_hashes(
+		&self,
+		hashes: &[ExtrinsicHash<ChainApi>],
+	) -> Vec<Option<TransactionFor<ChainApi>>> {
+		self.pool.validated_pool().by_hashes(hashes)
+	}
+
+	/// Refreshes the timestamp of the transaction with given hash to now, without revalidating
+	/// it.
+	///
+	/// Intended to be used when the transaction is known to be still valid (e.g. it was
+	/// revalidated in other view). Note that the timestamp drives the age-based ordering of
+	/// revalidation and eviction, so the touched transaction will be considered as the newest
+	/// one. Returns `false` if the transaction is not in the view.
+	pub(super) fn touch(&self, tx_hash: &ExtrinsicHash<ChainApi>) -> bool {
+		let touched = self.pool.validated_pool().touch(tx_hash);
+		trace!(target: LOG_TARGET, ?tx_hash, touched, at_hash = ?self.at.hash, "view::touch");
+		touched
 	}
 
-	/// Returns true if the transaction with given hash is already imported into the view.
+	/// Returns true if the transaction with given hash is already imported into the view (or is
+	/// temporarily banned).
 	pub(super) fn is_imported(&self, tx_hash: &ExtrinsicHash<ChainApi>) -> bool {
-		const IGNORE_BANNED: bool = false;
-		self.pool.validated_pool().check_is_known(tx_hash, IGNORE_BANNED).is_err()
+		self.import_state(tx_hash) != ImportState::Unknown
+	}
+
+	/// Returns the state of the transaction with given hash in the view.
+	///
+	/// Both the ready and future queues are checked under a single acquisition of the internal
+	/// pool lock. Intended for diagnostics (i.e. answering "where is my transaction").
+	pub(super) fn import_state(&self, tx_hash: &ExtrinsicHash<ChainApi>) -> ImportState {
+		let validated_pool = self.pool.validated_pool();
+		{
+			let pool = validated_pool.pool.read();
+			if pool.ready_by_hash(tx_hash).is_some() {
+				return ImportState::Ready
+			}
+			if pool.is_imported(tx_hash) {
+				return ImportState::Future
+			}
+		}
+		if validated_pool.is_banned(tx_hash) {
+			ImportState::Banned
+		} else {
+			ImportState::Unknown
+		}
+	}
+
+	/// Removes a single transaction from the inner pool, leaving its dependents in the pool.
+	///
+	/// Unlike [`Self::remove_subtree`] the transactions depending on the removed one are not
+	/// removed: the ready ones are moved to the future queue (triggering the Future event) and
+	/// will be promoted back once a transaction providing the required tags is imported.
+	///
+	/// This is an advanced operation: dependents may become orphaned (i.e. stuck in the future
+	/// queue) if the removed transaction is never replaced by a transaction providing the same
+	/// tags. The removed transaction is not banned and no events are triggered for it, notifying
+	/// the listeners is up to the caller. Note that this only affects the view: the transaction
+	/// is kept in the mempool, so it may be re-submitted to the view when the mempool is
+	/// synchronized with it (e.g. on the view update), unless it is also removed from there.
+	///
+	/// Returns `true` if the transaction was present in the view.
+	pub(super) fn force_remove(&self, tx_hash: &ExtrinsicHash<ChainApi>) -> bool {
+		let found = self.pool.validated_pool().remove_single(tx_hash).is_some();
+
+		trace!(target: LOG_TARGET, ?tx_hash, found, at_hash = ?self.at.hash, "view::force_remove");
+
+		self.refresh_status();
+		found
+	}
+
+	/// Evicts the `count` oldest transactions from the inner pool.
+	///
+	/// The age of the transaction is determined by its [`TimedTransactionSource`] timestamp, both
+	/// ready and future transactions are considered, transactions without the timestamp are
+	/// skipped. Every evicted transaction is removed together with its subtree (dependents), the
+	/// listeners are notified as if the transactions were dropped due to the pool limits.
+	///
+	/// Intended to be used as a primitive for handling the memory pressure. Returns the hashes of
+	/// all removed transactions, including the dependents.
+	pub(super) fn evict_oldest(&self, count: usize) -> Vec<ExtrinsicHash<ChainApi>> {
+		if count == 0 {
+			return Vec::new()
+		}
+
+		let oldest = {
+			let pool = self.pool.validated_pool().pool.read();
+			let mut timed = pool
+				.ready()
+				.filter_map(|tx| tx.source.timestamp.map(|t| (t, tx.hash)))
+				.chain(pool.futures().filter_map(|tx| tx.source.timestamp.map(|t| (t, tx.hash))))
+				.collect::<Vec<_>>();
+			timed.sort_by_key(|(timestamp, _)| *timestamp);
+			timed.into_iter().take(count).map(|(_, hash)| hash).collect::<Vec<_>>()
+		};
+
+		let removed = self
+			.remove_subtree(&oldest, false, |listener, hash| listener.limits_enforced(&hash))
+			.into_iter()
+			.map(|tx| tx.hash)
+			.collect::<Vec<_>>();
+
+		debug!(
+			target: LOG_TARGET,
+			requested = count,
+			evicted = oldest.len(),
+			removed = removed.len(),
+			at_hash = ?self.at.hash,
+			"view::evict_oldest"
+		);
+
+		removed
+	}
+
+	/// Evicts all the transactions sent by `sender` from the inner pool.
+	///
+	/// The sender of the transaction is provided by the runtime-specific `extract` closure. Both
+	/// ready and future transactions are considered. Every matching transaction is removed
+	/// together with its subtree (dependents) and banned, so it is not accepted again for the ban
+	/// time. The listeners are notified that the transactions were dropped.
+	///
+	/// Intended for the account-targeted cleanup (e.g. of a detected spammer). Returns the hashes
+	/// of all removed transactions, including the dependents.
+	pub(super) fn evict_by_sender<AccountId: PartialEq>(
+		&self,
+		extract: impl Fn(&ExtrinsicFor<ChainApi>) -> Option<AccountId>,
+		sender: &AccountId,
+	) -> Vec<ExtrinsicHash<ChainApi>> {
+		let matching = {
+			let pool = self.pool.validated_pool().pool.read();
+			let matches = |xt: &ExtrinsicFor<ChainApi>| extract(xt).is_some_and(|s| s == *sender);
+			pool.ready()
+				.filter(|tx| matches(&tx.data))
+				.map(|tx| tx.hash)
+				.chain(pool.futures().filter(|tx| matches(&tx.data)).map(|tx| tx.hash))
+				.collect::<Vec<_>>()
+		};
+
+		let removed = self
+			.remove_subtree(&matching, true, |listener, hash| listener.dropped(&hash))
+			.into_iter()
+			.map(|tx| tx.hash)
+			.collect::<Vec<_>>();
+
+		debug!(
+			target: LOG_TARGET,
+			matching = matching.len(),
+			removed = removed.len(),
+			at_hash = ?self.at.hash,
+			"view::evict_by_sender"
+		);
+
+		removed
+	}
+
+	/// Removes all the ready and future transactions from the inner pool.
+	///
+	/// The view itself is kept alive at its block (together with its metrics and the lifetime
+	/// tracking), so it can be reused instead of being dropped and recreated (e.g. for the test
+	/// resets or administrative flushes). The listeners are notified that the transactions were
+	/// dropped. The transactions are not banned and can be submitted again.
+	///
+	/// Returns the hashes of all removed transactions.
+	pub(super) fn clear(&self) -> Vec<ExtrinsicHash<ChainApi>> {
+		let hashes = {
+			let pool = self.pool.validated_pool().pool.read();
+			pool.ready()
+				.map(|tx| tx.hash)
+				.chain(pool.futures().map(|tx| tx.hash))
+				.collect::<Vec<_>>()
+		};
+
+		let removed = self
+			.remove_subtree(&hashes, false, |listener, hash| listener.dropped(&hash))
+			.into_iter()
+			.map(|tx| tx.hash)
+			.collect::<Vec<_>>();
+
+		debug!(target: LOG_TARGET, removed = removed.len(), at_hash = ?self.at.hash, "view::clear");
+
+		removed
+	}
+
+	/// Replaces the transaction with the given `old_hash` by `new_xt`, but only if the new
+	/// transaction is better.
+	///
+	/// The new transaction is validated and its priority is compared against the priority of the
+	/// old one (either ready or future). The swap is committed only if the new priority is strictly
+	/// higher (the standard fee-bump acceptance rule), otherwise the view is left unchanged.
+	/// Transactions depending on the old one are kept in the view. The old transaction is reported
+	/// as usurped by the new one only once the new one was successfully submitted, if the
+	/// submission fails the old transaction is restored and the error is returned.
+	///
+	/// Note: the new transaction is submitted to the view only, updating the mempool is up to the
+	/// caller.
+	pub(super) async fn replace_if_better(
+		&self,
+		old_hash: ExtrinsicHash<ChainApi>,
+		source: TimedTransactionSource,
+		new_xt: ExtrinsicFor<ChainApi>,
+	) -> Result<ReplaceOutcome, ChainApi::Error> {
+		let Some(old_priority) = self.priority_of(&old_hash) else {
+			return Ok(ReplaceOutcome::NotFound)
+		};
+
+		self.pre_validate(&new_xt)?;
+		let at = self.validation_block();
+		let (new_hash, validated) = self
+			.pool
+			.verify_one(at.hash, at.number, source, new_xt, graph::CheckBannedBeforeVerify::Yes)
+			.await;
+		let new_tx = match validated {
+			ValidatedTransaction::Valid(tx) => tx,
+			ValidatedTransaction::Invalid(_, error) | ValidatedTransaction::Unknown(_, error) =>
+				return Err(error),
+		};
+
+		if new_tx.priority <= old_priority {
+			trace!(
+				target: LOG_TARGET,
+				?old_hash,
+				?new_hash,
+				old_priority,
+				new_priority = new_tx.priority,
+				at_hash = ?self.at.hash,
+				"view::replace_if_better: not better"
+			);
+			return Ok(ReplaceOutcome::NotBetter)
+		}
+
+		// The old transaction may have been removed in the meantime (e.g. by concurrent
+		// revalidation). Its dependents are kept in the view, in the future queue until the
+		// tags they require are provided again.
+		let validated_pool = self.pool.validated_pool();
+		let Some(old_tx) = validated_pool.remove_single(&old_hash) else {
+			return Ok(ReplaceOutcome::NotFound)
+		};
+
+		let submitted = validated_pool
+			.submit(std::iter::once(ValidatedTransaction::Valid(new_tx)))
+			.remove(0);
+		if let Err(error) = submitted {
+			let restored = validated_pool
+				.submit(std::iter::once(ValidatedTransaction::Valid(
+					graph::base_pool::Transaction::unwrap_or_duplicate(old_tx),
+				)))
+				.remove(0);
+			if let Err(restore_error) = restored {
+				warn!(
+					target: LOG_TARGET,
+					?old_hash,
+					%restore_error,
+					at_hash = ?self.at.hash,
+					"view::replace_if_better: failed to restore the old transaction"
+				);
+				validated_pool.dispatch_events(|listener| listener.dropped(&old_hash));
+			}
+			self.refresh_status();
+			return Err(error)
+		}
+		validated_pool.dispatch_events(|listener| listener.usurped(&old_hash, &new_hash));
+		self.refresh_status();
+
+		debug!(
+			target: LOG_TARGET,
+			?old_hash,
+			?new_hash,
+			at_hash = ?self.at.hash,
+			"view::replace_if_better: replaced"
+		);
+		Ok(ReplaceOutcome::Replaced)
+	}
+
+	/// Returns the priority of the ready or future transaction with the given hash.
+	fn priority_of(&self, tx_hash: &ExtrinsicHash<ChainApi>) -> Option<TransactionPriority> {
+		let pool = self.pool.validated_pool().pool.read();
+		pool.ready_by_hash(tx_hash)
+			.map(|tx| tx.priority)
+			.or_else(|| pool.futures().find(|tx| tx.hash == *tx_hash).map(|tx| tx.priority))
+	}
+
+	/// Re-adds the transactions recently removed (and banned) from the view.
+	///
+	/// The ban of the given transactions is lifted first, so they are not rejected as
+	/// [`TxPoolError::TemporarilyBanned`], then they are submitted as in [`Self::submit_many`].
+	/// Useful when the removal (e.g. by [`Self::remove_subtree`]) turned out to be premature. The
+	/// results are returned in the order given by `txs`.
+	pub(super) async fn resubmit_removed(
+		&self,
+		txs: Vec<(TimedTransactionSource, ExtrinsicFor<ChainApi>)>,
+	) -> Vec<Result<ValidatedPoolSubmitOutcome<ChainApi>, ChainApi::Error>> {
+		let validated_pool = self.pool.validated_pool();
+		let hashes = txs
+			.iter()
+			.map(|(_, xt)| validated_pool.api().hash_and_length(xt).0)
+			.collect::<Vec<_>>();
+		validated_pool.unban(&hashes);
+		debug!(target: LOG_TARGET, count = hashes.len(), at_hash = ?self.at.hash, "view::resubmit_removed");
+		self.submit_many(txs).await
 	}
 
 	/// Removes the whole transaction subtree from the inner pool.
@@ -665,8 +2661,1467 @@ where
 			ExtrinsicHash<ChainApi>,
 		),
 	{
-		self.pool
-			.validated_pool()
-			.remove_subtree(hashes, ban_transactions, listener_action)
+		let removed =
+			self.pool
+				.validated_pool()
+				.remove_subtree(hashes, ban_transactions, listener_action);
+		self.refresh_status();
+		removed
+	}
+}
+
+impl<ChainApi: graph::ChainApi> View<ChainApi> {
+	/// Pins the view, deferring its removal on finalization.
+	///
+	/// The view is kept in the pool as long as the returned guard is alive (e.g. for the duration
+	/// of the block building), even if the finalization would otherwise remove it. Once unpinned,
+	/// the view is removed on the next finalization.
+	///
+	/// Note: a guard which is never dropped keeps the view (together with all its transactions)
+	/// in the pool forever, so the guards shall not be stored for longer than needed.
+	pub(super) fn pin(self: &Arc<Self>) -> ViewPin<ChainApi> {
+		self.pins.fetch_add(1, Ordering::Relaxed);
+		trace!(target: LOG_TARGET, at_hash = ?self.at.hash, "view::pin");
+		ViewPin { view: self.clone() }
+	}
+
+	/// Releases the pin acquired by [`Self::pin`].
+	fn unpin(&self) {
+		self.pins.fetch_sub(1, Ordering::Relaxed);
+		trace!(target: LOG_TARGET, at_hash = ?self.at.hash, "view::unpin");
+	}
+
+	/// Returns `true` if there is at least one alive guard pinning the view.
+	pub(super) fn is_pinned(&self) -> bool {
+		self.pins.load(Ordering::Relaxed) > 0
+	}
+}
+
+impl<ChainApi: graph::ChainApi> Drop for View<ChainApi> {
+	fn drop(&mut self) {
+		if self.last_revalidation.get_mut().is_none() {
+			trace!(
+				target: LOG_TARGET,
+				at_hash = ?self.at.hash,
+				"view::drop: view removed without revalidation"
+			);
+			self.metrics.report(|metrics| metrics.view_removed_without_revalidation.inc());
+		}
+		self.revalidation_metrics.flush();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		common::tests::{uxt, TestApi},
+		fork_aware_txpool::revalidation_worker::RevalidationQueue,
+		graph::ChainApi as _,
+	};
+	use futures::{executor::block_on, FutureExt};
+	use sp_runtime::transaction_validity::UnknownTransaction;
+	use substrate_test_runtime::{
+		AccountId, Extrinsic, ExtrinsicBuilder, Transfer, TransferData, H256,
+	};
+	use substrate_test_runtime_client::Sr25519Keyring::{self, Alice, Bob};
+
+	/// Creates a view at block `number` of the test chain.
+	fn view_at(
+		api: &Arc<TestApi>,
+		number: u64,
+		options: graph::Options,
+		metrics: PrometheusMetrics,
+	) -> View<TestApi> {
+		let at = api.expect_hash_and_number(number);
+		View::new(api.clone(), at, options, metrics, true.into(), None).0
+	}
+
+	/// Creates a view at the genesis block with default options and without metrics.
+	fn new_view(api: &Arc<TestApi>) -> Arc<View<TestApi>> {
+		Arc::new(view_at(api, 0, Default::default(), Default::default()))
+	}
+
+	/// Creates a transfer from Alice with the given nonce.
+	fn transfer(nonce: u64) -> Extrinsic {
+		transfer_from(Alice, nonce)
+	}
+
+	/// Creates a transfer from the given account with the given nonce.
+	///
+	/// Note: the tags of the test transfers depend on the nonce only.
+	fn transfer_from(from: Sr25519Keyring, nonce: u64) -> Extrinsic {
+		uxt(Transfer {
+			from: from.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce,
+		})
+	}
+
+	/// Submits the transfer with the given nonce as an external, timestamped transaction.
+	fn submit(view: &View<TestApi>, nonce: u64) -> ExtrinsicHash<TestApi> {
+		submit_xt(view, transfer(nonce))
+	}
+
+	/// Submits the extrinsic as an external, timestamped transaction.
+	fn submit_xt(view: &View<TestApi>, xt: Extrinsic) -> ExtrinsicHash<TestApi> {
+		let source = TimedTransactionSource::new_external(true);
+		block_on(view.submit_one(source, Arc::from(xt))).unwrap().hash()
+	}
+
+	/// Returns the hashes of the future transactions of the view.
+	fn future_hashes(view: &View<TestApi>) -> Vec<ExtrinsicHash<TestApi>> {
+		view.iter_future().map(|(tx_hash, _, _)| tx_hash).collect()
+	}
+
+	/// Runs the complete revalidation of the view without the background worker.
+	fn revalidate(view: &Arc<View<TestApi>>) {
+		let queue = Arc::new(RevalidationQueue::new());
+		block_on(View::start_background_revalidation(view.clone(), queue));
+		block_on(view.finish_revalidation());
+	}
+
+	/// Returns the value of the counter registered under the given name.
+	fn counter_value(registry: &prometheus_endpoint::Registry, name: &str) -> f64 {
+		registry
+			.gather()
+			.iter()
+			.find(|family| family.get_name() == name)
+			.map(|family| family.get_metric()[0].get_counter().get_value())
+			.expect("counter is registered")
+	}
+
+	#[test]
+	fn view_reports_transactions_ages() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+		assert!(view.transactions_ages().is_empty());
+
+		submit(&view, 0);
+		submit(&view, 2);
+		std::thread::sleep(Duration::from_millis(10));
+
+		let ages = view.transactions_ages();
+		assert_eq!(ages.len(), 2);
+		assert!(ages.iter().all(|age| *age >= Duration::from_millis(10)));
+	}
+
+	#[test]
+	fn view_with_metrics_disabled_does_not_report() {
+		let api = Arc::new(TestApi::default());
+
+		let reported_revalidations = |disable_metrics: bool| {
+			let registry = prometheus_endpoint::Registry::new();
+			let metrics = PrometheusMetrics::new(Some(&registry));
+			let view = view_at(&api, 0, Default::default(), metrics);
+			let view = Arc::new(if disable_metrics { view.with_metrics_disabled() } else { view });
+			revalidate(&view);
+
+			registry
+				.gather()
+				.iter()
+				.find(|family| {
+					family.get_name() == "substrate_sub_txpool_view_revalidation_duration_seconds"
+				})
+				.map(|family| family.get_metric()[0].get_histogram().get_sample_count())
+				.unwrap_or_default()
+		};
+
+		assert_eq!(reported_revalidations(false), 1);
+		assert_eq!(reported_revalidations(true), 0);
+	}
+
+	#[test]
+	fn revalidation_records_last_revalidation_instant() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+		assert!(view.last_revalidation_instant().is_none());
+
+		let before = Instant::now();
+		revalidate(&view);
+		assert!(view.last_revalidation_instant().is_some_and(|instant| instant >= before));
+	}
+
+	#[test]
+	fn revalidation_works_with_configured_channel_capacities() {
+		let api = Arc::new(TestApi::default());
+
+		for capacity in [0, 1, 4] {
+			let options = graph::Options {
+				revalidation_finish_request_channel_capacity: capacity,
+				revalidation_result_channel_capacity: capacity,
+				..Default::default()
+			};
+			let view = Arc::new(view_at(&api, 0, options, Default::default()));
+			let tx_hash = submit(&view, 0);
+			api.invalidate.lock().insert(tx_hash);
+
+			revalidate(&view);
+			assert!(view.status().is_empty(), "capacity: {capacity}");
+			api.invalidate.lock().clear();
+		}
+	}
+
+	#[test]
+	fn revalidation_of_empty_view_exits_early() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+		assert_eq!(view.validated_count(), 0);
+
+		revalidate(&view);
+		assert!(view.last_revalidation_instant().is_some());
+		assert!(api.validation_requests().is_empty());
+
+		submit(&view, 0);
+		submit(&view, 2);
+		assert_eq!(view.validated_count(), 2);
+		assert_eq!((view.status().ready, view.status().future), (1, 1));
+	}
+
+	#[test]
+	fn revalidation_in_progress_reflects_worker_state() {
+		let api = Arc::new(TestApi::default());
+
+		// background worker is not polled, so the revalidation never completes.
+		let view = new_view(&api);
+		let (queue, _worker) = RevalidationQueue::new_with_worker(1, Default::default());
+		assert!(!view.revalidation_in_progress());
+		block_on(View::start_background_revalidation(view.clone(), Arc::new(queue)));
+		assert!(view.revalidation_in_progress());
+
+		// without background worker the revalidation is completed in place.
+		let view = new_view(&api);
+		block_on(View::start_background_revalidation(
+			view.clone(),
+			Arc::new(RevalidationQueue::new()),
+		));
+		assert!(!view.revalidation_in_progress());
+		block_on(view.finish_revalidation());
+		assert!(!view.revalidation_in_progress());
+	}
+
+	#[test]
+	fn finish_revalidation_does_not_wait_for_stalled_worker() {
+		let api = Arc::new(TestApi::default());
+
+		// background worker is not polled, so the revalidation result is never sent.
+		let view = new_view(&api);
+		let (queue, _worker) = RevalidationQueue::new_with_worker(1, Default::default());
+		block_on(View::start_background_revalidation(view.clone(), Arc::new(queue)));
+
+		let started = Instant::now();
+		block_on(view.finish_revalidation());
+		assert!(started.elapsed() >= FINISH_REVALIDATION_REQUEST_TIMEOUT);
+		assert!(!view.revalidation_in_progress());
+	}
+
+	#[test]
+	fn on_finalized_cancels_revalidation_and_freezes_view() {
+		let api = Arc::new(TestApi::default());
+		let block0 = api.expect_hash_and_number(0);
+		let source = TimedTransactionSource::new_external(false);
+		let xt = |nonce| Arc::from(transfer(nonce));
+
+		let view = new_view(&api);
+		submit(&view, 0);
+
+		// background worker is not polled, so the revalidation stays in progress.
+		let (queue, _worker) = RevalidationQueue::new_with_worker(1, Default::default());
+		block_on(View::start_background_revalidation(view.clone(), Arc::new(queue)));
+		assert!(view.revalidation_in_progress());
+
+		// finalized block below the view is ignored.
+		let below = HashAndNumber { hash: H256::repeat_byte(1), number: 0 };
+		let later = view_at(&api, 1, Default::default(), Default::default());
+		later.on_finalized(&below);
+		assert!(!later.is_frozen());
+
+		view.on_finalized(&block0);
+		assert!(view.is_frozen());
+		assert!(!view.revalidation_in_progress());
+		assert_eq!(view.status().ready, 1);
+
+		let result = block_on(view.submit_one(source.clone(), xt(1)));
+		assert!(matches!(result, Err(TxPoolError::ImmediatelyDropped)));
+		let results =
+			block_on(view.submit_many(vec![(source.clone(), xt(1)), (source.clone(), xt(2))]));
+		assert!(results.iter().all(|r| matches!(r, Err(TxPoolError::ImmediatelyDropped))));
+		assert_eq!(view.status().ready, 1);
+	}
+
+	#[test]
+	fn revalidation_refreshes_timestamp_and_keeps_source() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let source = TimedTransactionSource::new_local(true);
+		let submitted_at = source.timestamp.unwrap();
+		let xt_hash = block_on(view.submit_one(source, transfer(0).into())).unwrap().hash();
+		std::thread::sleep(Duration::from_millis(10));
+
+		revalidate(&view);
+
+		assert_eq!(api.validation_requests().len(), 2);
+		let tx = view.pool.validated_pool().ready_by_hash(&xt_hash).unwrap();
+		assert_eq!(tx.source.source, TransactionSource::Local);
+		assert!(tx.source.timestamp.unwrap() > submitted_at);
+	}
+
+	#[test]
+	fn revalidation_keeps_unknown_local_transactions_if_configured() {
+		let api = Arc::new(TestApi::default());
+		let options =
+			graph::Options { keep_unknown_local_transactions: true, ..Default::default() };
+		let view = Arc::new(view_at(&api, 0, options, Default::default()));
+
+		let local =
+			block_on(view.submit_one(TimedTransactionSource::new_local(false), transfer(0).into()))
+				.unwrap()
+				.hash();
+		let external = submit(&view, 1);
+		assert_eq!(view.status().ready, 2);
+
+		api.unknown.lock().extend([local, external]);
+		revalidate(&view);
+
+		let validated_pool = view.pool.validated_pool();
+		assert!(validated_pool.ready_by_hash(&local).is_some());
+		assert!(validated_pool.ready_by_hash(&external).is_none());
+		assert_eq!(view.status().ready, 1);
+	}
+
+	#[test]
+	fn revalidation_counts_only_removals_decided_by_policy() {
+		let api = Arc::new(TestApi::default());
+		let removed_by_policy = |registry: &prometheus_endpoint::Registry| {
+			counter_value(
+				registry,
+				"substrate_sub_txpool_view_revalidation_removed_by_policy_txs_total",
+			)
+		};
+
+		// no policy is configured, the transaction is removed by default.
+		let registry = prometheus_endpoint::Registry::new();
+		let metrics = PrometheusMetrics::new(Some(&registry));
+		let view = Arc::new(view_at(&api, 0, Default::default(), metrics));
+		let xt_hash = submit(&view, 0);
+		api.unknown.lock().insert(xt_hash);
+		revalidate(&view);
+		assert_eq!(view.status().ready, 0);
+		assert_eq!(removed_by_policy(&registry), 0.0);
+
+		// the per-source policy keeps local transactions only, the external one is removed.
+		let registry = prometheus_endpoint::Registry::new();
+		let metrics = PrometheusMetrics::new(Some(&registry));
+		let options =
+			graph::Options { keep_unknown_local_transactions: true, ..Default::default() };
+		let view = Arc::new(view_at(&api, 0, options, metrics));
+		let xt_hash = submit(&view, 0);
+		api.unknown.lock().insert(xt_hash);
+		revalidate(&view);
+		assert_eq!(view.status().ready, 0);
+		assert_eq!(removed_by_policy(&registry), 1.0);
+	}
+
+	#[test]
+	fn revalidation_quarantines_unknown_transactions_if_configured() {
+		let api = Arc::new(TestApi::default());
+		let options =
+			graph::Options { unknown_quarantine_max_retries: Some(1), ..Default::default() };
+		let view = Arc::new(view_at(&api, 0, options, Default::default()));
+
+		let (recovering, unknown) = (submit(&view, 0), submit(&view, 1));
+		api.unknown.lock().extend([recovering, unknown]);
+
+		// both transactions are quarantined, but kept in the view
+		revalidate(&view);
+		assert_eq!(view.status().ready, 2);
+		assert_eq!(view.stats_snapshot().quarantined, 2);
+
+		// the recovering transaction leaves the quarantine, the unknown one exceeds the retries
+		api.unknown.lock().remove(&recovering);
+		revalidate(&view);
+		let validated_pool = view.pool.validated_pool();
+		assert!(validated_pool.ready_by_hash(&recovering).is_some());
+		assert!(validated_pool.ready_by_hash(&unknown).is_none());
+		assert_eq!(view.stats_snapshot().quarantined, 0);
+	}
+
+	#[test]
+	fn quarantine_is_updated_only_when_revalidation_result_is_applied() {
+		let api = Arc::new(TestApi::default());
+		let options =
+			graph::Options { unknown_quarantine_max_retries: Some(1), ..Default::default() };
+		let view = Arc::new(view_at(&api, 0, options, Default::default()));
+
+		let xt_hash = submit(&view, 0);
+		let tx = view.pool.validated_pool().ready_by_hash(&xt_hash).unwrap();
+		let unknown = Ok(Err(UnknownTransaction::CannotLookup.into()));
+		let result =
+			view.process_validation_results(&view.validation_block(), vec![(unknown, xt_hash, tx)]);
+		assert_eq!(view.stats_snapshot().quarantined, 0);
+
+		view.apply_revalidation_result(result);
+		assert_eq!(view.status().ready, 1);
+		assert_eq!(view.stats_snapshot().quarantined, 1);
+	}
+
+	#[test]
+	fn submit_many_rejects_oversized_transactions() {
+		let api = Arc::new(TestApi::default());
+		let xt = transfer(0);
+		let oversized = ExtrinsicBuilder::new_include_data(vec![0; 1024]).build();
+		let limit = api.hash_and_length(&xt).1;
+
+		let options = graph::Options { max_extrinsic_size: Some(limit), ..Default::default() };
+		let view = view_at(&api, 0, options, Default::default());
+
+		let source = TimedTransactionSource::new_external(false);
+		let results = block_on(view.submit_many([
+			(source.clone(), Arc::from(oversized.clone())),
+			(source, Arc::from(xt)),
+		]));
+		assert!(matches!(
+			results[0],
+			Err(TxPoolError::TooLarge { size, limit: l })
+				if size == api.hash_and_length(&oversized).1 && l == limit
+		));
+		assert!(results[1].is_ok());
+		assert!(!api.validation_requests().contains(&oversized));
+		assert_eq!(view.status().ready, 1);
+	}
+
+	#[test]
+	fn submit_many_results_are_aligned_with_input() {
+		let api = Arc::new(TestApi::default());
+		let limit = api.hash_and_length(&transfer(0)).1;
+		let options = graph::Options { max_extrinsic_size: Some(limit), ..Default::default() };
+		let view = view_at(&api, 0, options, Default::default());
+
+		// valid, invalid and oversized (rejected before validation) transactions interleaved
+		let oversized = ExtrinsicBuilder::new_include_data(vec![0; 1024]).build();
+		let xts = vec![
+			transfer(0),
+			transfer(1),
+			oversized.clone(),
+			transfer(2),
+			transfer(3),
+			oversized,
+			transfer(4),
+		];
+		for invalid in [&xts[1], &xts[4]] {
+			api.invalidate.lock().insert(api.hash_and_length(invalid).0);
+		}
+
+		let source = TimedTransactionSource::new_external(false);
+		let results = block_on(
+			view.submit_many(xts.iter().map(|xt| (source.clone(), Arc::from(xt.clone())))),
+		);
+		assert_eq!(results.len(), xts.len());
+		for (index, (xt, result)) in xts.iter().zip(results.iter()).enumerate() {
+			match index {
+				1 | 4 => assert!(matches!(result, Err(TxPoolError::InvalidTransaction(_)))),
+				2 | 5 => assert!(matches!(result, Err(TxPoolError::TooLarge { .. }))),
+				_ => assert_eq!(result.as_ref().unwrap().hash(), api.hash_and_length(xt).0),
+			}
+		}
+	}
+
+	#[test]
+	fn submit_many_with_retry_retries_transient_failures_only() {
+		let api = Arc::new(TestApi::default());
+		let registry = prometheus_endpoint::Registry::new();
+		let metrics = PrometheusMetrics::new(Some(&registry));
+		let view = view_at(&api, 0, Default::default(), metrics);
+
+		let (valid, unknown, invalid) = (transfer(0), transfer(1), transfer(2));
+		api.unknown.lock().insert(api.hash_and_length(&unknown).0);
+		api.invalidate.lock().insert(api.hash_and_length(&invalid).0);
+
+		let source = TimedTransactionSource::new_external(false);
+		let xts = [&valid, &unknown, &invalid]
+			.into_iter()
+			.map(|xt| (source.clone(), Arc::from(xt.clone())))
+			.collect();
+		let results = block_on(view.submit_many_with_retry(xts, 2, Duration::from_millis(1)));
+		assert!(results[0].is_ok());
+		assert!(matches!(results[1], Err(TxPoolError::UnknownTransaction(_))));
+		assert!(matches!(results[2], Err(TxPoolError::InvalidTransaction(_))));
+
+		let requests = api.validation_requests();
+		let count = |xt| requests.iter().filter(|request| *request == xt).count();
+		assert_eq!((count(&valid), count(&unknown), count(&invalid)), (1, 3, 1));
+		assert_eq!(counter_value(&registry, "substrate_sub_txpool_view_submit_retries_total"), 2.0);
+	}
+
+	#[test]
+	fn submit_many_fail_fast_stops_on_systemic_error() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let xts = (0..5).map(transfer).collect::<Vec<_>>();
+		api.invalidate.lock().insert(api.hash_and_length(&xts[1]).0);
+		api.fail_validation.lock().insert(api.hash_and_length(&xts[3]).0);
+
+		let source = TimedTransactionSource::new_external(false);
+		let (results, error) =
+			block_on(view.submit_many_fail_fast(
+				xts.iter().map(|xt| (source.clone(), Arc::from(xt.clone()))),
+			));
+		assert_eq!(results.len(), 3);
+		assert!(results[0].is_ok() && results[2].is_ok());
+		assert!(matches!(results[1], Err(TxPoolError::InvalidTransaction(_))));
+		assert!(matches!(error, Some(TxPoolError::InvalidBlockId(_))));
+
+		// the remaining transaction is not validated, the submitted ones are kept.
+		assert!(!api.validation_requests().contains(&xts[4]));
+		assert_eq!((view.status().ready, view.status().future), (1, 1));
+
+		let (results, error) =
+			block_on(view.submit_many_fail_fast([(source, Arc::from(xts[4].clone()))]));
+		assert!(results[0].is_ok() && error.is_none());
+	}
+
+	#[tokio::test]
+	async fn validation_timeout_rejects_stalled_transactions() {
+		let api = Arc::new(TestApi::default());
+		let registry = prometheus_endpoint::Registry::new();
+		let options = graph::Options {
+			validation_timeout: Some(Duration::from_millis(10)),
+			..Default::default()
+		};
+		let view = Arc::new(view_at(&api, 0, options, PrometheusMetrics::new(Some(&registry))));
+		let timeouts =
+			|| counter_value(&registry, "substrate_sub_txpool_view_validation_timeouts_total");
+
+		let xts = (0..2).map(transfer).collect::<Vec<_>>();
+		let hashes = xts.iter().map(|xt| api.hash_and_length(xt).0).collect::<Vec<_>>();
+		api.stall_validation.lock().insert(hashes[1]);
+
+		let source = TimedTransactionSource::new_external(false);
+		let results = view
+			.submit_many(xts.iter().map(|xt| (source.clone(), Arc::from(xt.clone()))))
+			.await;
+		assert_eq!(results[0].as_ref().unwrap().hash(), hashes[0]);
+		assert!(matches!(results[1], Err(TxPoolError::ValidationTimeout)));
+		assert_eq!(timeouts(), 1.0);
+
+		// the transaction which validation stalls during revalidation is removed.
+		api.stall_validation.lock().insert(hashes[0]);
+		View::start_background_revalidation(view.clone(), Arc::new(RevalidationQueue::new())).await;
+		view.finish_revalidation().await;
+		assert!(view.ready_hashes().is_empty());
+		assert_eq!(timeouts(), 2.0);
+	}
+
+	#[test]
+	fn validate_transaction_with_context_bypasses_recheck() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let xt = Arc::from(transfer(0));
+		let source = TransactionSource::External;
+		block_on(view.submit_one(TimedTransactionSource::new_external(false), xt.clone())).unwrap();
+		api.invalidate.lock().insert(api.hash_and_length(&xt).0);
+		let requests = api.validation_requests().len();
+
+		// the validity stored in the view is returned, the runtime is not called.
+		let validity =
+			block_on(view.validate_transaction_with_context(source, xt.clone(), false)).unwrap();
+		assert!(validity.is_ok());
+		assert_eq!(api.validation_requests().len(), requests);
+
+		let validity = block_on(view.validate_transaction_with_context(source, xt, true)).unwrap();
+		assert!(matches!(
+			validity,
+			Err(TransactionValidityError::Invalid(InvalidTransaction::Custom(_)))
+		));
+		assert_eq!(api.validation_requests().len(), requests + 1);
+	}
+
+	#[test]
+	fn resubmit_removed_lifts_the_ban() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let xt = transfer(0);
+		let xt_hash = api.hash_and_length(&xt).0;
+		let source = TimedTransactionSource::new_external(false);
+		let txs = vec![(source, Arc::from(xt))];
+
+		assert!(block_on(view.submit_many(txs.clone()))[0].is_ok());
+		let removed = view.remove_subtree(&[xt_hash], true, |_, _| {});
+		assert_eq!(removed.len(), 1);
+
+		// plain resubmission is rejected due to the ban.
+		let results = block_on(view.submit_many(txs.clone()));
+		assert!(matches!(results[0], Err(TxPoolError::TemporarilyBanned)));
+		assert!(view.ready_hashes().is_empty());
+
+		let results = block_on(view.resubmit_removed(txs));
+		assert_eq!(results[0].as_ref().unwrap().hash(), xt_hash);
+		assert_eq!(view.ready_hashes(), vec![xt_hash]);
+	}
+
+	#[test]
+	fn submit_many_reports_view_near_capacity() {
+		let api = Arc::new(TestApi::default());
+		let registry = prometheus_endpoint::Registry::new();
+		let options = graph::Options {
+			ready: graph::base_pool::Limit { count: 4, total_bytes: usize::MAX },
+			soft_limit_ratio: Some(0.5),
+			..Default::default()
+		};
+		let view = view_at(&api, 0, options, PrometheusMetrics::new(Some(&registry)));
+
+		let near_capacity =
+			|| counter_value(&registry, "substrate_sub_txpool_view_near_capacity_total");
+		let submit = |nonces: std::ops::Range<u64>| {
+			let xts = nonces.map(|nonce| {
+				(TimedTransactionSource::new_external(false), Arc::from(transfer(nonce)))
+			});
+			assert!(block_on(view.submit_many(xts)).iter().all(Result::is_ok));
+		};
+
+		// the soft limit is reached, but not crossed
+		submit(0..2);
+		assert_eq!(near_capacity(), 0.0);
+
+		submit(2..3);
+		assert_eq!(near_capacity(), 1.0);
+		submit(3..4);
+		assert_eq!(near_capacity(), 2.0);
+		assert_eq!(view.status().ready, 4);
+	}
+
+	#[test]
+	fn revalidation_reports_removal_reason_to_listener() {
+		let api = Arc::new(TestApi::default());
+		let block0 = api.expect_hash_and_number(0);
+
+		let (view, mut dropped_stream, _) = View::new(
+			api.clone(),
+			block0,
+			Default::default(),
+			Default::default(),
+			false.into(),
+			None,
+		);
+		let view = Arc::new(view);
+
+		let (unknown, invalid) = (submit(&view, 0), submit(&view, 1));
+		assert_eq!(view.status().ready, 2);
+
+		api.unknown.lock().insert(unknown);
+		api.invalidate.lock().insert(invalid);
+
+		revalidate(&view);
+		assert_eq!(view.status().ready, 0);
+
+		let events = std::iter::from_fn(|| dropped_stream.next().now_or_never().flatten())
+			.filter(|(_, status)| !matches!(status, TransactionStatus::Ready))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			events,
+			vec![(invalid, TransactionStatus::Invalid), (unknown, TransactionStatus::Dropped)]
+		);
+	}
+
+	#[test]
+	fn revalidation_removed_stream_reports_removed_transactions() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+		let mut removed_stream = view.revalidation_removed_stream();
+
+		let (kept, unknown, invalid) = (submit(&view, 0), submit(&view, 1), submit(&view, 2));
+		api.unknown.lock().insert(unknown);
+		api.invalidate.lock().insert(invalid);
+
+		revalidate(&view);
+
+		let removed = std::iter::from_fn(|| removed_stream.next().now_or_never().flatten())
+			.collect::<Vec<_>>();
+		assert_eq!(removed.len(), 2);
+		assert!(removed.contains(&unknown));
+		assert!(removed.contains(&invalid));
+		assert!(!removed.contains(&kept));
+	}
+
+	#[test]
+	fn validation_tracer_observes_view_validations() {
+		let api = Arc::new(TestApi::default());
+
+		let traced = Arc::new(Mutex::new(Vec::new()));
+		let tracer: ValidationTracer<TestApi> = {
+			let traced = traced.clone();
+			Arc::new(move |tx_hash, _| traced.lock().push(tx_hash))
+		};
+		let view = Arc::new(
+			view_at(&api, 0, Default::default(), Default::default())
+				.with_validation_tracer(Some(tracer)),
+		);
+
+		let (xt0, xt1) = (submit(&view, 0), submit(&view, 1));
+		assert!(traced.lock().is_empty());
+
+		revalidate(&view);
+
+		let traced = traced.lock();
+		assert_eq!(traced.len(), 2);
+		assert!(traced.contains(&xt0));
+		assert!(traced.contains(&xt1));
+	}
+
+	#[test]
+	fn new_view_from_other_rejects_unrelated_block() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let mismatched = HashAndNumber { hash: H256::from_low_u64_be(5), number: 3 };
+		assert!(view.try_new_from_other(&mismatched).is_err());
+
+		let unknown = HashAndNumber { hash: H256::repeat_byte(0xff), number: 1 };
+		assert!(view.try_new_from_other(&unknown).is_err());
+	}
+
+	#[test]
+	fn revalidation_at_best_block_if_configured() {
+		let api = Arc::new(TestApi::default());
+		let options = graph::Options { validate_at_best: true, ..Default::default() };
+		let view = Arc::new(view_at(&api, 0, options, Default::default()));
+
+		submit(&view, 0);
+		assert_eq!(view.status().ready, 1);
+
+		// nonce 0 is stale at block 1
+		*api.best_block.lock() = Some(api.expect_hash_from_number(1));
+
+		revalidate(&view);
+		assert_eq!(api.validation_requests().len(), 2);
+		assert_eq!(view.status().ready, 0);
+	}
+
+	#[test]
+	fn revalidation_aborted_after_consecutive_invalid_transactions() {
+		let api = Arc::new(TestApi::default());
+		let options = graph::Options { max_consecutive_invalid: Some(2), ..Default::default() };
+		let view = Arc::new(view_at(&api, 0, options, Default::default()));
+
+		let hashes = (0..3).map(|nonce| submit(&view, nonce)).collect::<Vec<_>>();
+		assert_eq!(view.status().ready, 3);
+		assert!(!view.requires_rebuild());
+
+		api.invalidate.lock().extend([hashes[0], hashes[1]]);
+		revalidate(&view);
+
+		// the last transaction was not revalidated
+		assert_eq!(api.validation_requests().len(), 5);
+		assert!(view.requires_rebuild());
+	}
+
+	#[test]
+	fn revalidation_results_resubmitted_in_chunks() {
+		let api = Arc::new(TestApi::default());
+		let options =
+			graph::Options { revalidation_resubmit_chunk_size: Some(2), ..Default::default() };
+		let view = Arc::new(view_at(&api, 0, options, Default::default()));
+
+		let hashes = (0..5).map(|nonce| submit(&view, nonce)).collect::<Vec<_>>();
+		assert_eq!(view.status().ready, 5);
+
+		api.invalidate.lock().insert(hashes[3]);
+		revalidate(&view);
+
+		let validated_pool = view.pool.validated_pool();
+		assert_eq!(view.status().ready, 3);
+		assert_eq!(view.status().future, 0);
+		assert!(hashes[..3].iter().all(|hash| validated_pool.ready_by_hash(hash).is_some()));
+	}
+
+	#[test]
+	fn revalidation_reports_transactions_not_reinstated() {
+		let api = Arc::new(TestApi::default());
+		let registry = prometheus_endpoint::Registry::new();
+		let options = graph::Options { reject_future_transactions: true, ..Default::default() };
+		let view = Arc::new(view_at(&api, 0, options, PrometheusMetrics::new(Some(&registry))));
+
+		let hashes = (0..2).map(|nonce| submit(&view, nonce)).collect::<Vec<_>>();
+		assert_eq!(view.status().ready, 2);
+
+		// revalidated transaction becomes future, so it is rejected on resubmission.
+		api.add_requirements.lock().insert(hashes[1]);
+
+		revalidate(&view);
+		assert_eq!((view.status().ready, view.status().future), (1, 0));
+		assert_eq!(
+			counter_value(
+				&registry,
+				"substrate_sub_txpool_view_revalidation_resubmit_failed_total"
+			),
+			1.0
+		);
+	}
+
+	#[test]
+	fn aggregated_revalidation_metrics_are_reported_on_flush() {
+		let api = Arc::new(TestApi::default());
+		let block0 = api.expect_hash_and_number(0);
+		let registry = prometheus_endpoint::Registry::new();
+		let metrics = PrometheusMetrics::new(Some(&registry));
+		let options = graph::Options {
+			revalidation_metrics_flush_interval: Some(Duration::from_secs(3600)),
+			..Default::default()
+		};
+		let resubmitted = || {
+			counter_value(&registry, "substrate_sub_txpool_view_revalidation_resubmitted_txs_total")
+		};
+
+		let view = Arc::new(view_at(&api, 0, options.clone(), metrics.clone()));
+		submit(&view, 0);
+		revalidate(&view);
+		assert_eq!(view.status().ready, 1);
+
+		// the update is buffered until flushed.
+		assert_eq!(resubmitted(), 0.0);
+		view.on_finalized(&block0);
+		assert_eq!(resubmitted(), 1.0);
+
+		// the buffer is also flushed when the view is dropped.
+		let view = Arc::new(view_at(&api, 0, options, metrics));
+		submit(&view, 0);
+		revalidate(&view);
+		assert_eq!(resubmitted(), 1.0);
+		drop(view);
+		assert_eq!(resubmitted(), 2.0);
+	}
+
+	#[test]
+	fn revalidation_removes_expired_transactions_without_validation() {
+		let api = Arc::new(TestApi::default());
+		let registry = prometheus_endpoint::Registry::new();
+		let view =
+			Arc::new(view_at(&api, 5, Default::default(), PrometheusMetrics::new(Some(&registry))));
+
+		let (fresh, expired) = (transfer(5), transfer(7));
+		block_on(
+			view.submit_one(TimedTransactionSource::new_external(false), fresh.clone().into()),
+		)
+		.unwrap();
+
+		// validated at block 1 with the longevity of 3, so valid till block 4.
+		let (expired_hash, bytes) = api.hash_and_length(&expired);
+		let validity = ValidTransaction {
+			priority: 4,
+			requires: vec![],
+			provides: vec![vec![7]],
+			longevity: 3,
+			propagate: true,
+		};
+		let validated = ValidatedTransaction::valid_at(
+			1,
+			expired_hash,
+			TimedTransactionSource::new_external(false),
+			Arc::from(expired.clone()),
+			bytes,
+			validity,
+		);
+		view.pool.validated_pool().submit(vec![validated]).remove(0).unwrap();
+		assert_eq!(view.status().ready, 2);
+		api.validation_requests.lock().clear();
+
+		revalidate(&view);
+
+		assert_eq!(view.status().ready, 1);
+		assert!(!view.ready_hashes().contains(&expired_hash));
+		assert_eq!(api.validation_requests(), vec![fresh]);
+		assert_eq!(
+			counter_value(&registry, "substrate_sub_txpool_view_revalidation_expired_txs_total"),
+			1.0
+		);
+	}
+
+	#[test]
+	fn revalidation_checks_expiry_at_validation_block() {
+		let api = Arc::new(TestApi::default());
+		let options = graph::Options { validate_at_best: true, ..Default::default() };
+		let view = Arc::new(view_at(&api, 2, options, Default::default()));
+
+		// validated at block 1 with the longevity of 3, so valid till block 4.
+		let expired = transfer(7);
+		let (expired_hash, bytes) = api.hash_and_length(&expired);
+		let validity = ValidTransaction {
+			priority: 4,
+			requires: vec![],
+			provides: vec![vec![7]],
+			longevity: 3,
+			propagate: true,
+		};
+		let validated = ValidatedTransaction::valid_at(
+			1,
+			expired_hash,
+			TimedTransactionSource::new_external(false),
+			Arc::from(expired),
+			bytes,
+			validity,
+		);
+		view.pool.validated_pool().submit(vec![validated]).remove(0).unwrap();
+		assert_eq!(view.status().ready, 1);
+
+		// still valid at the view's block, but expired at the best block.
+		*api.best_block.lock() = Some(api.expect_hash_from_number(5));
+		revalidate(&view);
+
+		assert_eq!(view.status().ready, 0);
+		assert!(api.validation_requests().is_empty());
+	}
+
+	#[test]
+	fn force_remove_keeps_dependents() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+		let xts = (0..3).map(transfer).collect::<Vec<_>>();
+		let hashes = xts.iter().map(|xt| submit_xt(&view, xt.clone())).collect::<Vec<_>>();
+
+		assert!(view.force_remove(&hashes[1]));
+		assert_eq!(view.ready_hashes(), vec![hashes[0]]);
+		assert_eq!(future_hashes(&view), vec![hashes[2]]);
+		assert!(!view.force_remove(&hashes[1]));
+
+		// re-submitting the removed transaction promotes its dependent back
+		submit_xt(&view, xts[1].clone());
+		assert_eq!(view.ready_hashes().len(), 3);
+		assert!(hashes.iter().all(|hash| view.ready_hashes().contains(hash)));
+		assert!(future_hashes(&view).is_empty());
+	}
+
+	#[test]
+	fn evict_oldest_removes_subtrees() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let xt0_hash = submit(&view, 0);
+		std::thread::sleep(Duration::from_millis(10));
+		let bob_hash = submit_xt(&view, transfer_from(Bob, 10));
+		std::thread::sleep(Duration::from_millis(10));
+		let xt1_hash = submit(&view, 1);
+
+		let evicted = view.evict_oldest(1);
+		assert_eq!(
+			evicted.into_iter().collect::<HashSet<_>>(),
+			HashSet::from([xt0_hash, xt1_hash])
+		);
+		assert_eq!(future_hashes(&view), vec![bob_hash]);
+
+		assert_eq!(view.evict_oldest(10), vec![bob_hash]);
+		assert!(view.status().is_empty());
+		assert!(view.evict_oldest(10).is_empty());
+	}
+
+	#[test]
+	fn evict_by_sender_removes_and_bans_subtrees() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let xts = [0, 1, 3].map(transfer);
+		let alice_hashes = xts.clone().map(|xt| submit_xt(&view, xt));
+		let bob_hash = submit_xt(&view, transfer_from(Bob, 10));
+
+		let extract = |xt: &Arc<Extrinsic>| TransferData::try_from(&**xt).ok().map(|t| t.from);
+		let evicted = view.evict_by_sender(extract, &Alice.into());
+		assert_eq!(evicted.into_iter().collect::<HashSet<_>>(), HashSet::from(alice_hashes));
+		assert_eq!(future_hashes(&view), vec![bob_hash]);
+
+		// the evicted transactions are banned
+		let source = TimedTransactionSource::new_external(true);
+		let result = block_on(view.submit_one(source, Arc::from(xts[0].clone())));
+		assert!(matches!(result, Err(TxPoolError::TemporarilyBanned)));
+		assert!(view.evict_by_sender(extract, &Alice.into()).is_empty());
+	}
+
+	#[test]
+	fn clear_removes_all_transactions() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+		let xts = [0, 1, 3].map(transfer);
+		let hashes = xts.clone().map(|xt| submit_xt(&view, xt));
+
+		assert_eq!(view.clear().into_iter().collect::<HashSet<_>>(), HashSet::from(hashes));
+		assert!(view.status().is_empty());
+		assert!(view.clear().is_empty());
+
+		// the view is kept alive and the transactions are not banned
+		submit_xt(&view, xts[0].clone());
+		assert_eq!(view.ready_hashes(), vec![hashes[0]]);
+	}
+
+	#[test]
+	fn submit_many_atomic_rolls_back_on_failure() {
+		let api = Arc::new(TestApi::default());
+		let block0 = api.expect_hash_and_number(0);
+		let (view, mut dropped_stream, _) = View::new(
+			api.clone(),
+			block0,
+			Default::default(),
+			Default::default(),
+			true.into(),
+			None,
+		);
+
+		let xt3_hash = submit(&view, 3);
+		let xts = (0..3).map(transfer).collect::<Vec<_>>();
+		api.invalidate.lock().insert(api.hash_and_length(&xts[2]).0);
+
+		let source = TimedTransactionSource::new_external(true);
+		let batch = |xts: &[Extrinsic]| {
+			xts.iter().map(|xt| (source.clone(), Arc::from(xt.clone()))).collect::<Vec<_>>()
+		};
+		let (index, error) = block_on(view.submit_many_atomic(batch(&xts))).map(drop).unwrap_err();
+		assert_eq!(index, 2);
+		assert!(matches!(error, TxPoolError::InvalidTransaction(InvalidTransaction::Custom(_))));
+		assert!(view.ready_hashes().is_empty());
+		assert_eq!(future_hashes(&view), vec![xt3_hash]);
+		let mut dropped = std::iter::from_fn(|| dropped_stream.next().now_or_never().flatten())
+			.filter_map(|(hash, status)| {
+				matches!(status, TransactionStatus::Dropped).then_some(hash)
+			})
+			.collect::<Vec<_>>();
+		dropped.sort();
+		let mut rolled_back =
+			xts[..2].iter().map(|xt| api.hash_and_length(xt).0).collect::<Vec<_>>();
+		rolled_back.sort();
+		assert_eq!(dropped, rolled_back);
+
+		let outcomes = block_on(view.submit_many_atomic(batch(&xts[..2]))).unwrap();
+		let hashes = outcomes.iter().map(|outcome| outcome.hash()).collect::<Vec<_>>();
+		assert_eq!(hashes, xts[..2].iter().map(|xt| api.hash_and_length(xt).0).collect::<Vec<_>>());
+		assert_eq!((view.status().ready, view.status().future), (2, 1));
+	}
+
+	#[test]
+	fn drain_into_moves_transactions() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+		let target = new_view(&api);
+
+		let hashes = [0, 1, 3].map(|nonce| submit(&view, nonce));
+		let data_hash = submit_xt(&view, ExtrinsicBuilder::new_include_data(vec![1]).build());
+		api.invalidate.lock().insert(hashes[2]);
+
+		let results = block_on(view.drain_into(&target));
+		assert_eq!(results.len(), 4);
+		let position = |tx_hash| results.iter().position(|(hash, _)| *hash == tx_hash).unwrap();
+		// ready transactions (in the dependency order) are followed by the future ones
+		assert!(position(hashes[0]) < position(hashes[1]));
+		assert_eq!(position(hashes[2]), 3);
+		for (tx_hash, result) in &results {
+			assert_eq!(result.is_ok(), *tx_hash != hashes[2]);
+		}
+
+		assert!(view.status().is_empty());
+		assert_eq!(
+			target.ready_hashes().into_iter().collect::<HashSet<_>>(),
+			HashSet::from([hashes[0], hashes[1], data_hash])
+		);
+		assert!(future_hashes(&target).is_empty());
+	}
+
+	#[test]
+	fn transactions_by_hashes_and_import_state_work() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let ready_hash = submit(&view, 0);
+		let future_hash = submit(&view, 2);
+		let banned = transfer(5);
+		let banned_hash = api.hash_and_length(&banned).0;
+		api.invalidate.lock().insert(banned_hash);
+		let source = TimedTransactionSource::new_external(true);
+		assert!(block_on(view.submit_one(source, Arc::from(banned))).is_err());
+		let unknown_hash = api.hash_and_length(&transfer(6)).0;
+
+		let txs = view.transactions_by_hashes(&[ready_hash, future_hash, unknown_hash]);
+		let hashes = txs.iter().map(|tx| tx.as_ref().map(|tx| tx.hash)).collect::<Vec<_>>();
+		assert_eq!(hashes, vec![Some(ready_hash), Some(future_hash), None]);
+
+		assert_eq!(view.import_state(&ready_hash), ImportState::Ready);
+		assert_eq!(view.import_state(&future_hash), ImportState::Future);
+		assert_eq!(view.import_state(&banned_hash), ImportState::Banned);
+		assert_eq!(view.import_state(&unknown_hash), ImportState::Unknown);
+		assert!(view.is_imported(&banned_hash));
+		assert!(!view.is_imported(&unknown_hash));
+	}
+
+	#[test]
+	fn touch_refreshes_transaction_timestamp() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+		let hashes = [submit(&view, 0), submit(&view, 2)];
+
+		let timestamps = || {
+			view.transactions_by_hashes(&hashes)
+				.into_iter()
+				.map(|tx| tx.unwrap().source.timestamp.unwrap())
+				.collect::<Vec<_>>()
+		};
+		let before = timestamps();
+		std::thread::sleep(Duration::from_millis(1));
+
+		assert!(hashes.iter().all(|tx_hash| view.touch(tx_hash)));
+		assert!(!view.touch(&api.hash_and_length(&transfer(3)).0));
+
+		let after = timestamps();
+		assert!(after.iter().zip(before.iter()).all(|(after, before)| after > before));
+		assert_eq!((view.status().ready, view.status().future), (1, 1));
+	}
+
+	#[test]
+	fn ready_hashes_and_ready_since_work() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let xt0_hash = submit(&view, 0);
+		std::thread::sleep(Duration::from_millis(10));
+		let since = Instant::now();
+		let hashes = [submit(&view, 1), submit(&view, 2)];
+		submit(&view, 4);
+
+		assert_eq!(
+			view.ready_hashes().into_iter().collect::<HashSet<_>>(),
+			HashSet::from([xt0_hash, hashes[0], hashes[1]])
+		);
+		assert_eq!(view.ready_since(since), hashes.to_vec());
+		assert!(view.ready_since(Instant::now()).is_empty());
+	}
+
+	#[test]
+	fn ready_iter_limited_works() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+		let hashes = (0..3).map(|nonce| submit(&view, nonce)).collect::<Vec<_>>();
+
+		let limited =
+			|n| view.ready_iter_limited(n).into_iter().map(|tx| tx.hash).collect::<Vec<_>>();
+		assert!(limited(0).is_empty());
+		assert_eq!(limited(2), hashes[..2].to_vec());
+		assert_eq!(limited(10), hashes);
+	}
+
+	#[test]
+	fn get_validity_works() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		submit(&view, 0);
+		let xt = ExtrinsicBuilder::new_include_data(vec![1]).build();
+		let xt_hash = submit_xt(&view, xt.clone());
+
+		let expected =
+			block_on(api.validate_transaction(view.hash(), TransactionSource::External, xt.into()))
+				.unwrap()
+				.unwrap();
+		assert_eq!(view.get_validity(&xt_hash), Some(expected));
+		assert!(view.get_validity(&api.hash_and_length(&transfer(1)).0).is_none());
+	}
+
+	#[test]
+	fn stage_commit_and_discard_works() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+		let child = api.expect_hash_from_number(1);
+
+		let xt = ExtrinsicBuilder::new_include_data(vec![1]).build();
+		let xt_hash = api.hash_and_length(&xt).0;
+		let staged = block_on(view.stage(child, Arc::from(xt.clone()))).unwrap();
+		assert_eq!((staged.hash, staged.validity.priority), (xt_hash, 9001));
+		assert!(view.status().is_empty());
+
+		let result = block_on(view.stage(child, Arc::from(xt.clone())));
+		assert!(matches!(result, Err(TxPoolError::AlreadyImported(_))));
+		// the transaction can only be staged at the child block
+		let result = block_on(view.stage(api.expect_hash_from_number(2), Arc::from(transfer(0))));
+		assert!(matches!(result, Err(TxPoolError::InvalidBlockId(_))));
+
+		assert_eq!(view.discard_staged(), vec![xt_hash]);
+		assert!(block_on(view.commit_staged()).is_empty());
+		assert!(view.status().is_empty());
+
+		block_on(view.stage(child, Arc::from(xt))).unwrap();
+		let results = block_on(view.commit_staged());
+		let hashes = results.into_iter().map(|result| result.unwrap().hash()).collect::<Vec<_>>();
+		assert_eq!(hashes, vec![xt_hash]);
+		assert_eq!(view.ready_hashes(), vec![xt_hash]);
+	}
+
+	#[test]
+	fn pending_extrinsics_and_ready_with_sources_work() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let xts = [transfer(0), transfer(1), ExtrinsicBuilder::new_include_data(vec![1]).build()];
+		let local = TimedTransactionSource::new_local(true);
+		block_on(view.submit_one(local, Arc::from(xts[1].clone()))).unwrap();
+		submit_xt(&view, xts[0].clone());
+		submit_xt(&view, xts[2].clone());
+		submit(&view, 3);
+
+		let pending = view.pending_extrinsics().into_iter().map(|xt| (*xt).clone());
+		assert_eq!(
+			pending.collect::<Vec<_>>(),
+			vec![xts[2].clone(), xts[0].clone(), xts[1].clone()]
+		);
+
+		let sources = view
+			.ready_with_sources()
+			.map(|(tx, source)| (tx.hash, source.source))
+			.collect::<Vec<_>>();
+		let expected =
+			[TransactionSource::External, TransactionSource::External, TransactionSource::Local];
+		assert_eq!(
+			sources,
+			[2, 0, 1]
+				.into_iter()
+				.map(|i| api.hash_and_length(&xts[i]).0)
+				.zip(expected)
+				.collect::<Vec<_>>()
+		);
+	}
+
+	#[test]
+	fn subscribe_status_works() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let mut status = view.subscribe_status();
+		assert!(!status.has_changed().unwrap());
+		assert!(status.borrow().is_empty());
+
+		submit(&view, 0);
+		submit(&view, 2);
+
+		// both submissions are observed as a single transition
+		assert!(status.has_changed().unwrap());
+		let current = status.borrow_and_update().clone();
+		assert_eq!((current.ready, current.future), (1, 1));
+		assert!(!status.has_changed().unwrap());
+
+		// rejected submission does not change the status
+		let source = TimedTransactionSource::new_external(true);
+		assert!(block_on(view.submit_one(source, Arc::from(transfer(0)))).is_err());
+		assert!(!status.has_changed().unwrap());
+
+		view.clear();
+		assert!(status.has_changed().unwrap());
+		assert!(status.borrow_and_update().is_empty());
+	}
+
+	#[test]
+	fn revalidate_with_budget_works() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let xt0_hash = submit(&view, 0);
+		submit_xt(&view, ExtrinsicBuilder::new_include_data(vec![1]).build());
+		submit_xt(&view, ExtrinsicBuilder::new_indexed_call(vec![1]).build());
+
+		let run = block_on(view.revalidate_with_budget(2));
+		assert_eq!(run, RevalidationRun { validated: 2, invalid: 0, remaining_budget: 0 });
+		assert_eq!(view.status().ready, 3);
+
+		api.invalidate.lock().insert(xt0_hash);
+		let run = block_on(view.revalidate_with_budget(5));
+		assert_eq!(run, RevalidationRun { validated: 3, invalid: 1, remaining_budget: 2 });
+		assert_eq!(view.status().ready, 2);
+	}
+
+	#[test]
+	fn submit_many_stream_yields_indexed_results() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let xts = [transfer(0), transfer(1), transfer(2)];
+		api.invalidate.lock().insert(api.hash_and_length(&xts[1]).0);
+
+		let source = TimedTransactionSource::new_external(true);
+		let results = block_on(
+			view.submit_many_stream(xts.iter().map(|xt| (source.clone(), Arc::from(xt.clone()))))
+				.collect::<Vec<_>>(),
+		);
+		assert_eq!(results.iter().map(|(index, _)| *index).collect::<Vec<_>>(), vec![0, 1, 2]);
+		assert_eq!(results[0].1.as_ref().unwrap().hash(), api.hash_and_length(&xts[0]).0);
+		assert!(matches!(results[1].1, Err(TxPoolError::InvalidTransaction(_))));
+		assert_eq!(results[2].1.as_ref().unwrap().hash(), api.hash_and_length(&xts[2]).0);
+	}
+
+	#[test]
+	fn submit_local_with_priority_works() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let xt = ExtrinsicBuilder::new_include_data(vec![1]).build();
+		let (outcome, priority) = view.submit_local_with_priority(Arc::from(xt.clone())).unwrap();
+		assert_eq!((outcome.hash(), priority), (api.hash_and_length(&xt).0, 9001));
+
+		let invalid = transfer(0);
+		api.invalidate.lock().insert(api.hash_and_length(&invalid).0);
+		assert!(view.submit_local_with_priority(Arc::from(invalid)).is_err());
+		assert_eq!(view.ready_hashes(), vec![outcome.hash()]);
+	}
+
+	#[test]
+	fn submit_local_many_results_are_aligned_with_input() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let xts = [transfer(0), transfer(1), transfer(2)];
+		api.invalidate.lock().insert(api.hash_and_length(&xts[1]).0);
+
+		let results = view.submit_local_many(xts.iter().cloned().map(Arc::from).collect());
+		assert_eq!(results.len(), 3);
+		assert_eq!(results[0].as_ref().unwrap().hash(), api.hash_and_length(&xts[0]).0);
+		assert!(matches!(results[1], Err(TxPoolError::InvalidTransaction(_))));
+		assert_eq!(results[2].as_ref().unwrap().hash(), api.hash_and_length(&xts[2]).0);
+		assert_eq!((view.status().ready, view.status().future), (1, 1));
+	}
+
+	#[test]
+	fn find_by_sender_nonce_works() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let ready_hash = submit(&view, 0);
+		let future_hash = submit(&view, 2);
+
+		let extract = |xt: &Arc<Extrinsic>| {
+			TransferData::try_from(&**xt)
+				.ok()
+				.map(|transfer| (transfer.from, transfer.nonce))
+		};
+		let alice = Alice.into();
+		assert_eq!(view.find_by_sender_nonce(extract, &alice, 0), Some(ready_hash));
+		assert_eq!(view.find_by_sender_nonce(extract, &alice, 2), Some(future_hash));
+		assert_eq!(view.find_by_sender_nonce(extract, &alice, 1), None);
+		assert_eq!(view.find_by_sender_nonce(extract, &Bob.into(), 0), None);
+	}
+
+	#[test]
+	fn estimate_block_fill_skips_heavy_subtrees() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		(0..3).for_each(|nonce| {
+			submit(&view, nonce);
+		});
+		submit_xt(&view, ExtrinsicBuilder::new_include_data(vec![1]).build());
+		assert_eq!(view.status().ready, 4);
+
+		// the transaction at nonce 1 is too heavy, so the one at nonce 2 is also skipped
+		let weight_of = |xt: &Arc<Extrinsic>| {
+			let heavy = TransferData::try_from(&**xt).is_ok_and(|transfer| transfer.nonce == 1);
+			Weight::from_parts(if heavy { 100 } else { 10 }, 1)
+		};
+		let estimate = |ref_time, proof_size| {
+			view.estimate_block_fill(Weight::from_parts(ref_time, proof_size), weight_of)
+		};
+
+		assert_eq!(estimate(50, 10), (2, Weight::from_parts(20, 2)));
+		assert_eq!(estimate(200, 10), (4, Weight::from_parts(130, 4)));
+		assert_eq!(estimate(200, 3), (3, Weight::from_parts(120, 3)));
+		assert_eq!(view.status().ready, 4);
+	}
+
+	#[test]
+	fn missing_tags_and_provides_index_work() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+		assert!(view.missing_tags().is_empty());
+		assert!(view.provides_index().is_empty());
+
+		submit(&view, 2);
+		let missing_tags2 = view.missing_tags();
+		assert_eq!(missing_tags2.len(), 1);
+
+		// the tag provided by future transaction is still missing
+		submit(&view, 1);
+		let missing_tags1 = view.missing_tags();
+		assert_eq!(missing_tags1.len(), 2);
+		assert!(missing_tags1.contains(&missing_tags2[0]));
+		assert!(view.provides_index().is_empty());
+
+		let xt0_hash = submit(&view, 0);
+		assert!(view.missing_tags().is_empty());
+		assert_eq!(view.provides_index().len(), 3);
+		assert_eq!(view.provides_index().get(&vec![0]), Some(&xt0_hash));
+	}
+
+	#[test]
+	fn replace_if_better_requires_higher_priority() {
+		let api = Arc::new(TestApi::default());
+		let view = new_view(&api);
+
+		let old_hash = submit(&view, 0);
+		let dependent_hash = submit(&view, 1);
+		let source = TimedTransactionSource::new_external(true);
+		let replace =
+			|xt: Extrinsic| block_on(view.replace_if_better(old_hash, source.clone(), xt.into()));
+
+		// the same priority is not enough
+		let same_priority = uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(3)),
+			amount: 5,
+			nonce: 0,
+		});
+		assert_eq!(replace(same_priority).unwrap(), ReplaceOutcome::NotBetter);
+		assert_eq!(view.ready_hashes().len(), 2);
+
+		let better = ExtrinsicBuilder::new_include_data(vec![1]).build();
+		assert_eq!(replace(better.clone()).unwrap(), ReplaceOutcome::Replaced);
+		assert_eq!(view.ready_hashes(), vec![api.hash_and_length(&better).0]);
+		// the dependent is kept in the view
+		assert_eq!(future_hashes(&view), vec![dependent_hash]);
+
+		assert_eq!(replace(transfer(0)).unwrap(), ReplaceOutcome::NotFound);
+	}
+
+	#[test]
+	fn replace_if_better_reports_usurped_only_on_success() {
+		let api = Arc::new(TestApi::default());
+		let block0 = api.expect_hash_and_number(0);
+		let (view, mut dropped_stream, _) = View::new(
+			api.clone(),
+			block0,
+			Default::default(),
+			Default::default(),
+			true.into(),
+			None,
+		);
+
+		let old_hash = submit(&view, 0);
+		let dependent_hash = submit(&view, 1);
+		submit_xt(&view, ExtrinsicBuilder::new_include_data(vec![2]).build());
+		let source = TimedTransactionSource::new_external(true);
+		let replace =
+			|xt: Extrinsic| block_on(view.replace_if_better(old_hash, source.clone(), xt.into()));
+		let mut removal_events = || {
+			std::iter::from_fn(|| dropped_stream.next().now_or_never().flatten())
+				.filter(|(_, status)| {
+					!matches!(status, TransactionStatus::Ready | TransactionStatus::Future)
+				})
+				.collect::<Vec<_>>()
+		};
+
+		// the new transaction conflicts with the included data of the same priority, so it is
+		// rejected on submission and the old transaction is restored
+		let rejected = ExtrinsicBuilder::new_include_data(vec![1]).build();
+		assert!(replace(rejected).is_err());
+		assert_eq!(view.ready_hashes().len(), 3);
+		assert!([old_hash, dependent_hash].iter().all(|h| view.ready_hashes().contains(h)));
+		assert!(removal_events().is_empty());
+
+		let better = ExtrinsicBuilder::new_indexed_call(vec![1]).build();
+		let better_hash = api.hash_and_length(&better).0;
+		assert_eq!(replace(better).unwrap(), ReplaceOutcome::Replaced);
+		assert_eq!(removal_events(), vec![(old_hash, TransactionStatus::Usurped(better_hash))]);
 	}
 }
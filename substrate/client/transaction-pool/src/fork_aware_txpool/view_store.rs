@@ -48,7 +48,7 @@ use std::{
 	sync::Arc,
 	time::Instant,
 };
-use tracing::{trace, warn};
+use tracing::{debug, trace, warn};
 
 /// Helper struct to maintain the context for pending transaction submission, executed for
 /// newly inserted views.
@@ -228,7 +228,7 @@ where
 					let xts = xts.clone();
 					async move {
 						(
-							view.at.hash,
+							view.hash(),
 							view.submit_many(xts)
 								.await
 								.into_iter()
@@ -445,7 +445,7 @@ where
 		at: Block::Hash,
 	) -> Option<Vec<Transaction<ExtrinsicHash<ChainApi>, ExtrinsicFor<ChainApi>>>> {
 		self.get_view_at(at, true)
-			.map(|(v, _)| v.pool.validated_pool().pool.read().futures().cloned().collect())
+			.map(|(v, _)| v.iter_future().map(|(_, tx, _)| (*tx).clone()).collect())
 	}
 
 	/// Collects all the transactions included in the blocks on the provided `tree_route` and
@@ -542,8 +542,8 @@ where
 						inactive_views.insert(hash, view);
 					});
 				});
-			active_views.insert(view.at.hash, view.clone());
-			most_recent_view_lock.replace(view.at.hash);
+			active_views.insert(view.hash(), view.clone());
+			most_recent_view_lock.replace(view.hash());
 		};
 		trace!(
 			target: LOG_TARGET,
@@ -593,8 +593,12 @@ where
 	) -> Vec<ExtrinsicHash<ChainApi>> {
 		let finalized_xts = self.finalize_route(finalized_hash, tree_route).await;
 		let finalized_number = self.api.block_id_to_number(&BlockId::Hash(finalized_hash));
-
+		let finalized = match finalized_number {
+			Ok(Some(number)) => Some(HashAndNumber { hash: finalized_hash, number }),
+			_ => None,
+		};
 		let mut dropped_views = vec![];
+		let mut pinned_views = vec![];
 		//clean up older then finalized
 		{
 			let mut active_views = self.active_views.write();
@@ -602,11 +606,15 @@ where
 			active_views.retain(|hash, v| {
 				let retain = match finalized_number {
 					Err(_) | Ok(None) => *hash == finalized_hash,
-					Ok(Some(n)) if v.at.number == n => *hash == finalized_hash,
-					Ok(Some(n)) => v.at.number > n,
+					Ok(Some(n)) if v.number() == n => *hash == finalized_hash,
+					Ok(Some(n)) => v.number() > n,
 				};
 				if !retain {
-					dropped_views.push(*hash);
+					if v.is_pinned() {
+						pinned_views.push((*hash, v.clone()));
+					} else {
+						dropped_views.push((*hash, v.clone()));
+					}
 				}
 				retain
 			});
@@ -614,14 +622,27 @@ where
 			inactive_views.retain(|hash, v| {
 				let retain = match finalized_number {
 					Err(_) | Ok(None) => false,
-					Ok(Some(n)) => v.at.number >= n,
+					Ok(Some(n)) => v.number() >= n,
 				};
 				if !retain {
-					dropped_views.push(*hash);
+					if v.is_pinned() {
+						return true
+					}
+					dropped_views.push((*hash, v.clone()));
 				}
 				retain
 			});
 
+			// pinned views are kept as inactive ones, until unpinned.
+			if !pinned_views.is_empty() {
+				debug!(
+					target: LOG_TARGET,
+					pinned_views = ?pinned_views.iter().map(|(hash, _)| hash).collect::<Vec<_>>(),
+					"handle_finalized: keeping pinned views"
+				);
+				inactive_views.extend(pinned_views);
+			}
+
 			trace!(
 				target: LOG_TARGET,
 				inactive_views = ?inactive_views.keys(),
@@ -629,9 +650,18 @@ where
 			);
 		}
 
+		// the views are finalized once the locks are released, so the views' clean up does not
+		// block the concurrent access to the view store.
+		for (_, view) in &dropped_views {
+			view.report_final_ages();
+			if let Some(finalized) = finalized.as_ref() {
+				view.on_finalized(finalized);
+			}
+		}
+
 		trace!(
 			target: LOG_TARGET,
-			?dropped_views,
+			dropped_views = ?dropped_views.iter().map(|(hash, _)| hash).collect::<Vec<_>>(),
 			"handle_finalized"
 		);
 
@@ -639,9 +669,9 @@ where
 		self.dropped_stream_controller.remove_transactions(finalized_xts.clone());
 
 		self.listener.remove_view(finalized_hash);
-		for view in dropped_views {
-			self.listener.remove_view(view);
-			self.dropped_stream_controller.remove_view(view);
+		for (view_hash, _) in dropped_views {
+			self.listener.remove_view(view_hash);
+			self.dropped_stream_controller.remove_view(view_hash);
 		}
 
 		finalized_xts
@@ -813,7 +843,7 @@ where
 			trace!(
 				target: LOG_TARGET,
 				?tx_hash,
-				at_hash = ?view.at.hash,
+				at_hash = ?view.hash(),
 				%error,
 				"replace_transaction: submit failed"
 			);
@@ -922,7 +952,7 @@ where
 			let mut active_views = self.active_views.write();
 			let mut inactive_views = self.inactive_views.write();
 			let mut f = |hash: &BlockHash<ChainApi>, v: &View<ChainApi>| -> bool {
-				let diff = at.number.saturating_sub(v.at.number);
+				let diff = at.number.saturating_sub(v.number());
 				if diff.into() > threshold.into() {
 					dropped_views.push(*hash);
 					false
@@ -20,7 +20,13 @@
 //!
 //! For a more full-featured pool, have a look at the `pool` module.
 
-use std::{cmp::Ordering, collections::HashSet, fmt, hash, sync::Arc, time::Instant};
+use std::{
+	cmp::Ordering,
+	collections::{HashMap, HashSet},
+	fmt, hash,
+	sync::Arc,
+	time::Instant,
+};
 
 use crate::LOG_TARGET;
 use sc_transaction_pool_api::{error, InPoolTransaction, PoolStatus};
@@ -37,7 +43,7 @@ use tracing::{trace, warn};
 
 use super::{
 	future::{FutureTransactions, WaitingTransaction},
-	ready::{BestIterator, ReadyTransactions, TransactionRef},
+	ready::{BestIterator, ReadyCursor, ReadyTransactions, TransactionRef},
 };
 
 /// Successful import result.
@@ -202,6 +208,13 @@ impl<Hash: Clone, Extrinsic: Clone> Transaction<Hash, Extrinsic> {
 			propagate: self.propagate,
 		}
 	}
+
+	/// Takes the transaction out of the shared pointer.
+	///
+	/// The transaction is duplicated only if the pointer is still shared.
+	pub fn unwrap_or_duplicate(tx: Arc<Self>) -> Self {
+		Arc::try_unwrap(tx).unwrap_or_else(|tx| tx.duplicate())
+	}
 }
 
 impl<Hash, Extrinsic> fmt::Debug for Transaction<Hash, Extrinsic>
@@ -258,6 +271,24 @@ pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 	recently_pruned_index: usize,
 }
 
+/// Shrinks the capacity of the map as much as possible.
+///
+/// Returns the estimated number of reclaimed bytes.
+pub(crate) fn shrink_map<K: hash::Hash + Eq, V>(map: &mut HashMap<K, V>) -> usize {
+	let capacity = map.capacity();
+	map.shrink_to_fit();
+	(capacity - map.capacity()) * std::mem::size_of::<(K, V)>()
+}
+
+/// Shrinks the capacity of the set as much as possible.
+///
+/// Returns the estimated number of reclaimed bytes.
+pub(crate) fn shrink_set<T: hash::Hash + Eq>(set: &mut HashSet<T>) -> usize {
+	let capacity = set.capacity();
+	set.shrink_to_fit();
+	(capacity - set.capacity()) * std::mem::size_of::<T>()
+}
+
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for BasePool<Hash, Ex> {
 	fn default() -> Self {
 		Self::new(false)
@@ -433,11 +464,48 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		self.ready.get()
 	}
 
+	/// Returns the next ready transaction for given cursor and advances it.
+	///
+	/// Unlike [`Self::ready`], the cursor does not take a snapshot of the ready queue, so the pool
+	/// does not need to stay locked between the steps. Refer to [`ReadyCursor`] for the details.
+	pub fn ready_next(
+		&self,
+		cursor: &mut ReadyCursor<Hash, Ex>,
+	) -> Option<Arc<Transaction<Hash, Ex>>> {
+		self.ready.next_with_cursor(cursor)
+	}
+
+	/// Returns the tags provided by the ready transactions, mapped to the providing transaction.
+	pub fn ready_provided_tags(&self) -> &HashMap<Tag, Hash> {
+		self.ready.provided_tags()
+	}
+
+	/// Shrinks the capacity of the internal collections as much as possible.
+	///
+	/// Intended to be called after a large number of transactions was removed from the pool.
+	/// Returns the estimated number of reclaimed bytes.
+	pub fn shrink_to_fit(&mut self) -> usize {
+		self.ready.shrink_to_fit() +
+			self.future.shrink_to_fit() +
+			self.recently_pruned.iter_mut().map(shrink_set).sum::<usize>()
+	}
+
+	/// Returns the hashes of ready transactions in the pool, in no particular order.
+	pub fn ready_hashes(&self) -> Vec<Hash> {
+		self.ready.hashes()
+	}
+
 	/// Returns an iterator over future transactions in the pool.
 	pub fn futures(&self) -> impl Iterator<Item = &Transaction<Hash, Ex>> {
 		self.future.all()
 	}
 
+	/// Returns an iterator over future transactions in the pool, including the set of tags every
+	/// transaction is waiting for.
+	pub fn futures_waiting(&self) -> impl Iterator<Item = &WaitingTransaction<Hash, Ex>> {
+		self.future.all_waiting()
+	}
+
 	/// Returns pool transactions given list of hashes.
 	///
 	/// Includes both ready and future pool. For every hash in the `hashes`
@@ -449,6 +517,17 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		ready.into_iter().zip(future).map(|(a, b)| a.or(b)).collect()
 	}
 
+	/// Refreshes the timestamp of the transaction (either ready or future) with given hash to now.
+	///
+	/// Returns `false` if the transaction is not in the pool.
+	pub fn touch(&mut self, hash: &Hash) -> bool
+	where
+		Ex: Clone,
+	{
+		let now = Instant::now();
+		self.ready.update_timestamp(hash, now) || self.future.update_timestamp(hash, now)
+	}
+
 	/// Returns pool transaction by hash.
 	pub fn ready_by_hash(&self, hash: &Hash) -> Option<Arc<Transaction<Hash, Ex>>> {
 		self.ready.by_hash(hash)
@@ -548,6 +627,45 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		removed
 	}
 
+	/// Removes a single transaction represented by the hash, keeping its dependents in the pool.
+	///
+	/// Unlike `remove_subtree`, the ready transactions depending on the removed one are not
+	/// dropped: as the tags they require are no longer provided, they are moved to the future
+	/// queue and will be promoted back once a transaction providing these tags is imported.
+	///
+	/// Returns the removed transaction (if it was present in the pool) together with the hashes
+	/// of the transactions moved to the future queue.
+	pub fn remove_single(&mut self, hash: &Hash) -> (Option<Arc<Transaction<Hash, Ex>>>, Vec<Hash>)
+	where
+		Ex: Clone,
+	{
+		if let Some(removed) = self.future.remove(&[hash.clone()]).pop() {
+			return (Some(removed), Vec::new())
+		}
+
+		let mut subtree = self.ready.remove_subtree(&[hash.clone()]).into_iter();
+		let Some(removed) = subtree.next() else { return (None, Vec::new()) };
+		debug_assert!(removed.hash == *hash, "remove_subtree returns the root first");
+
+		// The dependents (direct or not) require tags provided only by the removed subtree, so
+		// none of them can be ready anymore.
+		let demoted = subtree
+			.map(|tx| {
+				let tx = WaitingTransaction::new(
+					Transaction::unwrap_or_duplicate(tx),
+					self.ready.provided_tags(),
+					&self.recently_pruned,
+				);
+				debug_assert!(!tx.is_ready(), "dependents of removed transaction cannot be ready");
+				let hash = tx.transaction.hash.clone();
+				self.future.import(tx);
+				hash
+			})
+			.collect();
+
+		(Some(removed), demoted)
+	}
+
 	/// Removes and returns all transactions from the future queue.
 	pub fn clear_future(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		self.future.clear()
@@ -603,6 +721,13 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		PruneStatus { pruned, failed, promoted }
 	}
 
+	/// Get the total number of transactions (both ready and future) in the pool.
+	///
+	/// Cheaper than [`Self::status`], as the sizes of the queues are not gathered.
+	pub fn transactions_count(&self) -> usize {
+		self.ready.len() + self.future.len()
+	}
+
 	/// Get pool status.
 	pub fn status(&self) -> PoolStatus {
 		PoolStatus {
@@ -1088,6 +1213,58 @@ mod tests {
 		assert_eq!(pool.future.len(), 0);
 	}
 
+	#[test]
+	fn remove_single_moves_dependents_to_future() {
+		// given
+		let mut pool = pool();
+		pool.import(Transaction {
+			data: vec![1u8].into(),
+			hash: 1,
+			provides: vec![vec![1]],
+			..default_tx().clone()
+		})
+		.unwrap();
+		pool.import(Transaction {
+			data: vec![2u8].into(),
+			hash: 2,
+			requires: vec![vec![1]],
+			provides: vec![vec![2]],
+			..default_tx().clone()
+		})
+		.unwrap();
+		pool.import(Transaction {
+			data: vec![3u8].into(),
+			hash: 3,
+			requires: vec![vec![2]],
+			..default_tx().clone()
+		})
+		.unwrap();
+		assert_eq!(pool.ready().count(), 3);
+
+		// when
+		let (removed, demoted) = pool.remove_single(&1);
+
+		// then
+		assert_eq!(removed.map(|tx| tx.hash), Some(1));
+		assert_eq!(demoted, vec![2, 3]);
+		assert_eq!(pool.ready().count(), 0);
+		assert_eq!(pool.future.len(), 2);
+		assert!(matches!(pool.remove_single(&1), (None, demoted) if demoted.is_empty()));
+
+		// when the removed transaction is re-imported
+		pool.import(Transaction {
+			data: vec![1u8].into(),
+			hash: 1,
+			provides: vec![vec![1]],
+			..default_tx().clone()
+		})
+		.unwrap();
+
+		// then the dependents are promoted back
+		assert_eq!(pool.ready().count(), 3);
+		assert_eq!(pool.future.len(), 0);
+	}
+
 	#[test]
 	fn should_prune_ready_transactions() {
 		// given
@@ -1274,4 +1451,29 @@ source: TimedTransactionSource { source: TransactionSource::External, timestamp:
 		assert_eq!(pool.reject_future_transactions, true);
 		assert_eq!(pool.future.len(), 1);
 	}
+
+	#[test]
+	fn shrink_to_fit_reclaims_memory_after_removal() {
+		// given
+		let mut pool = pool();
+		for i in 0..64u64 {
+			pool.import(Transaction {
+				data: vec![i as u8],
+				hash: i,
+				provides: vec![vec![i as u8]],
+				..default_tx().clone()
+			})
+			.unwrap();
+		}
+		let hashes = (0..64u64).collect::<Vec<_>>();
+		assert_eq!(pool.remove_subtree(&hashes).len(), 64);
+
+		// when
+		let reclaimed = pool.shrink_to_fit();
+
+		// then
+		assert!(reclaimed > 0);
+		assert_eq!(pool.shrink_to_fit(), 0);
+		assert_eq!(pool.ready().count(), 0);
+	}
 }
@@ -26,7 +26,7 @@ use sp_core::hexdisplay::HexDisplay;
 use sp_runtime::transaction_validity::TransactionTag as Tag;
 use std::time::Instant;
 
-use super::base_pool::Transaction;
+use super::base_pool::{shrink_map, Transaction};
 use crate::{common::tracing_log_xt::log_xt_trace, LOG_TARGET};
 
 /// Transaction with partially satisfied dependencies.
@@ -168,6 +168,28 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 			.collect()
 	}
 
+	/// Shrinks the capacity of the internal collections as much as possible.
+	///
+	/// Returns the estimated number of reclaimed bytes.
+	pub fn shrink_to_fit(&mut self) -> usize {
+		shrink_map(&mut self.wanted_tags) + shrink_map(&mut self.waiting)
+	}
+
+	/// Replaces the timestamp of the transaction with given hash.
+	///
+	/// Returns `false` if the transaction is not in the queue.
+	pub fn update_timestamp(&mut self, hash: &Hash, timestamp: Instant) -> bool
+	where
+		Ex: Clone,
+	{
+		let Some(tx) = self.waiting.get_mut(hash) else { return false };
+
+		let mut transaction = tx.transaction.duplicate();
+		transaction.source.timestamp = Some(timestamp);
+		tx.transaction = Arc::new(transaction);
+		true
+	}
+
 	/// Removes transactions that provide any of tags in the given list.
 	///
 	/// Returns list of removed transactions.
@@ -258,6 +280,11 @@ impl<Hash: hash::Hash + Eq + Clone + std::fmt::Debug, Ex: std::fmt::Debug>
 		self.waiting.values().map(|waiting| &*waiting.transaction)
 	}
 
+	/// Returns iterator over all future transactions together with the tags they are waiting for.
+	pub fn all_waiting(&self) -> impl Iterator<Item = &WaitingTransaction<Hash, Ex>> {
+		self.waiting.values()
+	}
+
 	/// Removes and returns all future transactions.
 	pub fn clear(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		self.wanted_tags.clear();
@@ -33,6 +33,7 @@ mod ready;
 mod rotator;
 pub(crate) mod tracked_map;
 mod validated_pool;
+mod validation_cache;
 
 pub mod base_pool;
 pub mod watcher;
@@ -48,6 +49,7 @@ pub use validated_pool::{
 
 pub(crate) use self::pool::CheckBannedBeforeVerify;
 pub(crate) use listener::EventHandler;
+pub(crate) use ready::ReadyCursor;
 
 #[cfg(doc)]
 pub(crate) use validated_pool::ValidatedPool;
@@ -37,7 +37,7 @@ use tracing::{debug, trace};
 
 use super::{
 	base_pool as base,
-	validated_pool::{IsValidator, ValidatedPool, ValidatedTransaction},
+	validated_pool::{IsValidator, ResubmitOutcome, ValidatedPool, ValidatedTransaction},
 	EventHandler, ValidatedPoolSubmitOutcome,
 };
 
@@ -124,6 +124,14 @@ pub trait ChainApi: Send + Sync {
 		to: <Self::Block as BlockT>::Hash,
 	) -> Result<TreeRoute<Self::Block>, Self::Error>;
 
+	/// Returns the hash of the best block.
+	///
+	/// `None` is returned if the best block is not known. The default implementation always
+	/// returns `None`.
+	fn best_block_hash(&self) -> Result<Option<<Self::Block as BlockT>::Hash>, Self::Error> {
+		Ok(None)
+	}
+
 	/// Resolves block number by id.
 	fn resolve_block_number(
 		&self,
@@ -146,6 +154,104 @@ pub struct Options {
 	pub reject_future_transactions: bool,
 	/// How long the extrinsic is banned for.
 	pub ban_time: Duration,
+	/// Keep local transactions of unknown validity during the view revalidation.
+	///
+	/// If set, the fork-aware view revalidation removes the locally submitted transactions only
+	/// if they are found invalid. Local transactions for which the validity cannot be determined
+	/// are kept in the view. Transactions from other sources are not affected.
+	pub keep_unknown_local_transactions: bool,
+	/// Validate transactions at the best block instead of the view's block.
+	///
+	/// If set, the fork-aware view validates the locally submitted transactions and revalidates
+	/// its transactions at the best block (as reported by [`ChainApi::best_block_hash`]). If the
+	/// best block is not known, the view's block is used.
+	///
+	/// Note: the best block may belong to a different fork than the view's block. In such case
+	/// the validity of transactions kept in the view reflects the state of other fork: valid
+	/// transactions may be removed from the view (e.g. if they were included in the blocks of the
+	/// other fork), and invalid ones may be kept.
+	pub validate_at_best: bool,
+	/// The number of consecutive invalid transactions after which the view revalidation is
+	/// aborted.
+	///
+	/// Once the revalidation is aborted, the view is flagged to be rebuilt from scratch instead of
+	/// being cloned for the next block. Saves the CPU time when a large part of transactions
+	/// becomes invalid (e.g. after runtime upgrade). Disabled if `None`.
+	pub max_consecutive_invalid: Option<usize>,
+	/// The maximal number of revalidated transactions resubmitted into the view at once.
+	///
+	/// The results of the view revalidation are resubmitted in chunks of given size, the pool
+	/// lock is released between the chunks, so concurrent readers are not blocked for the whole
+	/// duration of resubmission. All the results are resubmitted at once if `None`.
+	pub revalidation_resubmit_chunk_size: Option<usize>,
+	/// The duration of the view's batch submission above which the submission is considered slow.
+	///
+	/// Slow submissions are logged at the `warn` level, together with the size of the batch and
+	/// the view's block hash. Disabled if `None`.
+	pub slow_submit_many_threshold: Option<Duration>,
+	/// The maximal number of cached transaction validation results.
+	///
+	/// If set, the results of the runtime validation are cached, keyed by the transaction hash and
+	/// the hash of the block the transaction was validated at. The cache is consulted before
+	/// calling into the runtime, so validating the same transaction at the same block again (e.g.
+	/// on resubmission after a reorg) is cheap. Only the valid and invalid results are cached, the
+	/// unknown validity is re-validated. Disabled if `None`.
+	pub validation_cache_size: Option<usize>,
+	/// The maximal duration of the runtime validation of a single transaction.
+	///
+	/// If set, the validation (both on submission and on revalidation) which does not finish in
+	/// time is abandoned and the transaction is rejected with
+	/// [`ValidationTimeout`](sc_transaction_pool_api::error::Error::ValidationTimeout) error.
+	/// Guards the pool against the transactions with pathologically slow validation. The
+	/// synchronous (blocking) validation is not bounded. Disabled if `None`.
+	pub validation_timeout: Option<Duration>,
+	/// The number of view revalidations for which the transaction of unknown validity is kept.
+	///
+	/// If set, the fork-aware view revalidation does not remove the transaction of unknown
+	/// validity right away. The transaction is quarantined and retried in the subsequent
+	/// revalidations, it is removed once its validity is still unknown after given number of
+	/// retries. Transactions revalidated successfully leave the quarantine. Reduces the churn
+	/// caused by the transiently unknown validity (e.g. during state sync). Disabled if `None`.
+	pub unknown_quarantine_max_retries: Option<usize>,
+	/// The maximal encoded length of the transaction submitted to the fork-aware view.
+	///
+	/// If set, the transactions exceeding the limit are rejected by the view's batch submission
+	/// with [`TooLarge`](sc_transaction_pool_api::error::Error::TooLarge) error before they are
+	/// validated, saving the runtime call. Disabled if `None`.
+	pub max_extrinsic_size: Option<usize>,
+	/// The maximal number of revalidations executed concurrently by the fork-aware pool.
+	///
+	/// Bounds the total CPU time spent on the background revalidation of the views (and the
+	/// mempool). The excess revalidation requests are queued and admitted as the ongoing
+	/// revalidations complete. At least one revalidation is always admitted.
+	pub max_concurrent_revalidations: usize,
+	/// The fill ratio of the ready or future queue above which the view is considered to be near
+	/// its capacity (e.g. `0.9`).
+	///
+	/// If set, the fork-aware view's batch submission leaving the queue (by the count or the
+	/// total bytes) filled above the given ratio of its limit is reported by the
+	/// `view_near_capacity` metric and a rate-limited warning. This gives the operators lead time
+	/// before the transactions start to be dropped. The limits enforcement is not affected.
+	/// Disabled if `None`.
+	pub soft_limit_ratio: Option<f64>,
+	/// The capacity of the channel carrying the request to finish the background view
+	/// revalidation (from the maintain thread to the revalidation worker).
+	///
+	/// Values below `1` are treated as `1`.
+	pub revalidation_finish_request_channel_capacity: usize,
+	/// The capacity of the channel carrying the results of the background view revalidation
+	/// (from the revalidation worker to the maintain thread).
+	///
+	/// Values below `1` are treated as `1`.
+	pub revalidation_result_channel_capacity: usize,
+	/// The interval at which the fork-aware view's revalidation metrics are reported.
+	///
+	/// If set, the revalidation counters (e.g. the number of invalid or resubmitted transactions)
+	/// are accumulated and flushed to the Prometheus registry once the interval has elapsed (and
+	/// when the view is finalized or dropped), instead of being reported on every revalidation.
+	/// Reduces the metrics churn on the nodes maintaining many views. The counters are reported
+	/// immediately if `None`.
+	pub revalidation_metrics_flush_interval: Option<Duration>,
 }
 
 impl Default for Options {
@@ -155,6 +261,20 @@ impl Default for Options {
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
 			reject_future_transactions: false,
 			ban_time: Duration::from_secs(60 * 30),
+			keep_unknown_local_transactions: false,
+			validate_at_best: false,
+			max_consecutive_invalid: None,
+			revalidation_resubmit_chunk_size: None,
+			slow_submit_many_threshold: None,
+			validation_cache_size: None,
+			validation_timeout: None,
+			unknown_quarantine_max_retries: None,
+			max_extrinsic_size: None,
+			max_concurrent_revalidations: 1,
+			soft_limit_ratio: None,
+			revalidation_finish_request_channel_capacity: 1,
+			revalidation_result_channel_capacity: 1,
+			revalidation_metrics_flush_interval: None,
 		}
 	}
 }
@@ -246,8 +366,10 @@ impl<B: ChainApi, L: EventHandler<B>> Pool<B, L> {
 		source: base::TimedTransactionSource,
 		xt: ExtrinsicFor<B>,
 	) -> Result<ValidatedPoolSubmitOutcome<B>, B::Error> {
-		let res = self.submit_at(at, std::iter::once((source, xt))).await.pop();
-		res.expect("One extrinsic passed; one result returned; qed")
+		let (_, tx) = self
+			.verify_one(at.hash, at.number, source, xt, CheckBannedBeforeVerify::Yes)
+			.await;
+		self.validated_pool.submit_single(tx)
 	}
 
 	/// Import a single extrinsic and starts to watch its progress in the pool.
@@ -264,18 +386,22 @@ impl<B: ChainApi, L: EventHandler<B>> Pool<B, L> {
 	}
 
 	/// Resubmit some transaction that were validated elsewhere.
+	///
+	/// Returns the final outcome of every affected transaction, refer to
+	/// [`ValidatedPool::resubmit`] for more details.
 	pub fn resubmit(
 		&self,
 		revalidated_transactions: IndexMap<ExtrinsicHash<B>, ValidatedTransactionFor<B>>,
-	) {
+	) -> HashMap<ExtrinsicHash<B>, ResubmitOutcome> {
 		let now = Instant::now();
-		self.validated_pool.resubmit(revalidated_transactions);
+		let outcomes = self.validated_pool.resubmit(revalidated_transactions);
 		trace!(
 			target: LOG_TARGET,
 			duration = ?now.elapsed(),
 			status = ?self.validated_pool.status(),
 			"Resubmitted transaction."
 		);
+		outcomes
 	}
 
 	/// Prunes known ready transactions.
@@ -501,8 +627,7 @@ impl<B: ChainApi, L: EventHandler<B>> Pool<B, L> {
 
 		let validation_result = self
 			.validated_pool
-			.api()
-			.validate_transaction(block_hash, source.clone().into(), xt.clone())
+			.validate_transaction(hash, block_hash, source.clone().into(), xt.clone())
 			.await;
 
 		let status = match validation_result {
@@ -637,6 +762,70 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn should_reuse_cached_validation_result() {
+		// given
+		let options = Options { validation_cache_size: Some(16), ..Default::default() };
+		let api = Arc::new(TestApi::default());
+		let pool = Pool::new_with_staticly_sized_rotator(options, true.into(), api.clone());
+		let xt: ExtrinsicFor<TestApi> = uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce: 0,
+		})
+		.into();
+		let hash = block_on(pool.submit_one(&api.expect_hash_and_number(0), SOURCE, xt.clone()))
+			.map(|outcome| outcome.hash())
+			.unwrap();
+		pool.validated_pool().remove_subtree(&[hash], false, |_, _| {});
+
+		// when
+		block_on(pool.submit_one(&api.expect_hash_and_number(0), SOURCE, xt.clone())).unwrap();
+
+		// then
+		assert_eq!(api.validation_requests().len(), 1);
+		assert_eq!(pool.validated_pool().take_validation_cache_hits(), 1);
+		assert_eq!(pool.validated_pool().ready().map(|v| v.hash).collect::<Vec<_>>(), vec![hash]);
+
+		// when validated at other block (nonce 0 is stale at block 1)
+		pool.validated_pool().remove_subtree(&[hash], false, |_, _| {});
+		let result = block_on(pool.submit_one(&api.expect_hash_and_number(1), SOURCE, xt));
+		assert!(result.is_err());
+
+		// then
+		assert_eq!(api.validation_requests().len(), 2);
+		assert_eq!(pool.validated_pool().take_validation_cache_hits(), 0);
+	}
+
+	#[test]
+	fn should_not_cache_unknown_validity() {
+		// given
+		let options = Options { validation_cache_size: Some(16), ..Default::default() };
+		let api = Arc::new(TestApi::default());
+		let pool = Pool::new_with_staticly_sized_rotator(options, true.into(), api.clone());
+		let xt: ExtrinsicFor<TestApi> = uxt(Transfer {
+			from: Alice.into(),
+			to: AccountId::from_h256(H256::from_low_u64_be(2)),
+			amount: 5,
+			nonce: 0,
+		})
+		.into();
+		let hash = api.hash_and_length(&xt).0;
+		api.unknown.lock().insert(hash);
+		let result = block_on(pool.submit_one(&api.expect_hash_and_number(0), SOURCE, xt.clone()));
+		assert!(result.is_err());
+
+		// when
+		api.unknown.lock().clear();
+		block_on(pool.submit_one(&api.expect_hash_and_number(0), SOURCE, xt)).unwrap();
+
+		// then
+		assert_eq!(api.validation_requests().len(), 2);
+		assert_eq!(pool.validated_pool().take_validation_cache_hits(), 0);
+		assert_eq!(pool.validated_pool().ready().map(|v| v.hash).collect::<Vec<_>>(), vec![hash]);
+	}
+
 	#[test]
 	fn should_reject_if_temporarily_banned() {
 		// given
@@ -21,6 +21,7 @@ use std::{
 	collections::{BTreeSet, HashMap, HashSet},
 	hash,
 	sync::Arc,
+	time::Instant,
 };
 
 use crate::LOG_TARGET;
@@ -30,7 +31,7 @@ use sp_runtime::{traits::Member, transaction_validity::TransactionTag as Tag};
 use tracing::trace;
 
 use super::{
-	base_pool::Transaction,
+	base_pool::{shrink_map, Transaction},
 	future::WaitingTransaction,
 	tracked_map::{self, TrackedMap},
 };
@@ -238,6 +239,18 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 		self.ready.read().values().fold(init, f)
 	}
 
+	/// Shrinks the capacity of the internal collections as much as possible.
+	///
+	/// Returns the estimated number of reclaimed bytes.
+	pub fn shrink_to_fit(&mut self) -> usize {
+		shrink_map(&mut self.provided_tags) + self.ready.write().shrink_to_fit()
+	}
+
+	/// Returns the hashes of all transactions in the queue, in no particular order.
+	pub fn hashes(&self) -> Vec<Hash> {
+		self.ready.read().keys().cloned().collect()
+	}
+
 	/// Returns true if given transaction is part of the queue.
 	pub fn contains(&self, hash: &Hash) -> bool {
 		self.ready.read().contains_key(hash)
@@ -257,6 +270,31 @@ impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
 			.collect()
 	}
 
+	/// Replaces the timestamp of the transaction with given hash.
+	///
+	/// The ordering of ready transactions does not depend on the timestamp, so the transaction
+	/// keeps its position in the queue. Returns `false` if the transaction is not in the queue.
+	pub fn update_timestamp(&mut self, hash: &Hash, timestamp: Instant) -> bool
+	where
+		Ex: Clone,
+	{
+		let mut ready = self.ready.write();
+		let Some(tx) = ready.get_mut(hash) else { return false };
+
+		let mut transaction = tx.transaction.transaction.duplicate();
+		transaction.source.timestamp = Some(timestamp);
+		let updated = TransactionRef {
+			transaction: Arc::new(transaction),
+			insertion_id: tx.transaction.insertion_id,
+		};
+
+		if self.best.remove(&tx.transaction) {
+			self.best.insert(updated.clone());
+		}
+		tx.transaction = updated;
+		true
+	}
+
 	/// Removes a subtree of transactions from the ready pool.
 	///
 	/// NOTE removing a transaction will also cause a removal of all transactions that depend on
@@ -578,6 +616,78 @@ impl<Hash: hash::Hash + Member, Ex> Iterator for BestIterator<Hash, Ex> {
 	}
 }
 
+/// Cursor over the ready transactions, yielding them in the same order as [`BestIterator`].
+///
+/// Unlike [`BestIterator`], the cursor does not take a snapshot of the queue: it only keeps its
+/// position within the `best` set and the transactions unlocked by the ones already yielded,
+/// every step looks the transactions up in the current state of the queue (refer to
+/// [`ReadyTransactions::next_with_cursor`]). Hence the queue does not need to stay locked between
+/// the steps, but the transactions imported in the meantime may be skipped and the removed ones
+/// are not yielded.
+pub struct ReadyCursor<Hash, Ex> {
+	/// The last transaction taken from the `best` set of the queue.
+	best_position: Option<TransactionRef<Hash, Ex>>,
+	/// Transactions unlocked by the yielded ones, with all their requirements satisfied.
+	unlocked: BTreeSet<TransactionRef<Hash, Ex>>,
+	/// Transactions unlocked by the yielded ones, still awaiting some of their requirements.
+	awaiting: HashMap<Hash, (usize, TransactionRef<Hash, Ex>)>,
+}
+
+impl<Hash, Ex> Default for ReadyCursor<Hash, Ex> {
+	fn default() -> Self {
+		Self { best_position: None, unlocked: Default::default(), awaiting: Default::default() }
+	}
+}
+
+impl<Hash: hash::Hash + Member + Serialize, Ex> ReadyTransactions<Hash, Ex> {
+	/// Returns the next ready transaction for given cursor and advances it.
+	///
+	/// Transactions are returned in the same order as by the iterator returned from
+	/// [`Self::get`], refer to [`ReadyCursor`] for the details.
+	pub fn next_with_cursor(
+		&self,
+		cursor: &mut ReadyCursor<Hash, Ex>,
+	) -> Option<Arc<Transaction<Hash, Ex>>> {
+		let ready = self.ready.read();
+		loop {
+			let next_best = match &cursor.best_position {
+				Some(position) => self.best.range(..position).next_back(),
+				None => self.best.last(),
+			};
+			let best = match (next_best, cursor.unlocked.last()) {
+				(Some(best), Some(unlocked)) if unlocked > best => cursor.unlocked.pop_last()?,
+				(Some(best), _) => {
+					cursor.best_position = Some(best.clone());
+					best.clone()
+				},
+				(None, _) => cursor.unlocked.pop_last()?,
+			};
+
+			// The transaction may have been removed in the meantime.
+			let Some(ready_tx) = ready.get(&best.transaction.hash) else { continue };
+
+			for hash in &ready_tx.unlocks {
+				let res = if let Some((satisfied, tx_ref)) = cursor.awaiting.remove(hash) {
+					Some((satisfied + 1, tx_ref))
+				} else {
+					ready.get(hash).map(|next| (next.requires_offset + 1, next.transaction.clone()))
+				};
+				if let Some((satisfied, tx_ref)) = res {
+					if satisfied >= tx_ref.transaction.requires.len() {
+						cursor.unlocked.insert(tx_ref);
+					} else {
+						cursor
+							.awaiting
+							.insert(tx_ref.transaction.hash.clone(), (satisfied, tx_ref));
+					}
+				}
+			}
+
+			return Some(best.transaction)
+		}
+	}
+}
+
 // See: https://github.com/rust-lang/rust/issues/40062
 fn remove_item<T: PartialEq>(vec: &mut Vec<T>, item: &T) {
 	if let Some(idx) = vec.iter().position(|i| i == item) {
@@ -720,6 +830,46 @@ mod tests {
 		assert_eq!(ready.best.len(), 1);
 	}
 
+	#[test]
+	fn cursor_returns_transactions_in_best_order() {
+		// given
+		let mut ready = ReadyTransactions::default();
+		populate_pool(&mut ready);
+		let mut tx8 = tx(8);
+		tx8.requires.clear();
+		tx8.provides = vec![vec![109]];
+		tx8.priority = 2;
+		import(&mut ready, tx8).unwrap();
+		let mut cursor = ReadyCursor::default();
+
+		// when
+		let order = std::iter::from_fn(|| ready.next_with_cursor(&mut cursor))
+			.map(|tx| tx.hash)
+			.collect::<Vec<_>>();
+
+		// then
+		assert_eq!(order, ready.get().map(|tx| tx.hash).collect::<Vec<_>>());
+		assert_eq!(order, vec![8, 1, 2, 3, 4, 5, 6, 7]);
+	}
+
+	#[test]
+	fn cursor_skips_transactions_removed_in_the_meantime() {
+		// given
+		let mut ready = ReadyTransactions::default();
+		populate_pool(&mut ready);
+		let mut cursor = ReadyCursor::default();
+		assert_eq!(ready.next_with_cursor(&mut cursor).map(|tx| tx.hash), Some(1));
+
+		// when
+		ready.remove_subtree(&[4]);
+
+		// then
+		let order = std::iter::from_fn(|| ready.next_with_cursor(&mut cursor))
+			.map(|tx| tx.hash)
+			.collect::<Vec<_>>();
+		assert_eq!(order, vec![2, 3, 7]);
+	}
+
 	#[test]
 	fn should_return_best_transactions_in_correct_order() {
 		// given
@@ -824,4 +974,23 @@ mod tests {
 		assert!(!tx1_unlocks.contains(&tx3.hash));
 		assert!(tx1_unlocks.contains(&tx4.hash));
 	}
+
+	#[test]
+	fn should_update_timestamp_without_changing_order() {
+		// given
+		let mut ready = ReadyTransactions::default();
+		populate_pool(&mut ready);
+		let order = ready.get().map(|tx| tx.hash).collect::<Vec<_>>();
+		let first = order[0];
+		let timestamp = Instant::now();
+
+		// when
+		assert!(ready.update_timestamp(&first, timestamp));
+		assert!(!ready.update_timestamp(&100, timestamp));
+
+		// then
+		assert_eq!(ready.by_hash(&first).unwrap().source.timestamp, Some(timestamp));
+		assert!(ready.best.iter().any(|tx| tx.transaction.source.timestamp == Some(timestamp)));
+		assert_eq!(ready.get().map(|tx| tx.hash).collect::<Vec<_>>(), order);
+	}
 }
@@ -102,6 +102,18 @@ impl<Hash: hash::Hash + Eq + Clone> PoolRotator<Hash> {
 		}
 	}
 
+	/// Lifts the ban of given set of hashes.
+	pub fn unban<'a>(&self, hashes: impl IntoIterator<Item = &'a Hash>)
+	where
+		Hash: 'a,
+	{
+		let mut banned = self.banned_until.write();
+
+		for hash in hashes {
+			banned.remove(hash);
+		}
+	}
+
 	/// Bans extrinsic if it's stale.
 	///
 	/// Returns `true` if extrinsic is stale and got banned.
@@ -201,6 +213,21 @@ mod tests {
 		assert!(!rotator.is_banned(&hash));
 	}
 
+	#[test]
+	fn should_unban() {
+		// given
+		let (hash, tx) = tx();
+		let rotator = rotator();
+		assert!(rotator.ban_if_stale(&Instant::now(), 1, &tx));
+		assert!(rotator.is_banned(&hash));
+
+		// when
+		rotator.unban(&[hash]);
+
+		// then
+		assert!(!rotator.is_banned(&hash));
+	}
+
 	#[test]
 	fn should_garbage_collect() {
 		// given
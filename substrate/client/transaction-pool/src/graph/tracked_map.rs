@@ -116,6 +116,11 @@ where
 		self.inner_guard.values()
 	}
 
+	/// Returns an iterator over all keys.
+	pub fn keys(&self) -> std::collections::hash_map::Keys<K, V> {
+		self.inner_guard.keys()
+	}
+
 	/// Returns the number of elements in the map.
 	pub fn len(&self) -> usize {
 		self.inner_guard.len()
@@ -133,6 +138,15 @@ where
 	K: Eq + std::hash::Hash,
 	V: Size,
 {
+	/// Shrinks the capacity of the map as much as possible.
+	///
+	/// Returns the estimated number of reclaimed bytes.
+	pub fn shrink_to_fit(&mut self) -> usize {
+		let capacity = self.inner_guard.capacity();
+		self.inner_guard.shrink_to_fit();
+		(capacity - self.inner_guard.capacity()) * std::mem::size_of::<(K, V)>()
+	}
+
 	/// Insert value and return previous (if any).
 	pub fn insert(&mut self, key: K, val: V) -> Option<V> {
 		let new_bytes = val.size();
@@ -18,18 +18,27 @@
 
 use std::{
 	collections::{HashMap, HashSet},
-	sync::Arc,
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Arc,
+	},
 };
 
 use crate::{common::tracing_log_xt::log_xt_trace, LOG_TARGET};
-use futures::channel::mpsc::{channel, Sender};
+use futures::{
+	channel::mpsc::{channel, Sender},
+	Future, FutureExt,
+};
 use indexmap::IndexMap;
 use parking_lot::{Mutex, RwLock};
 use sc_transaction_pool_api::{error, PoolStatus, ReadyTransactions, TransactionPriority};
 use sp_blockchain::HashAndNumber;
 use sp_runtime::{
 	traits::SaturatedConversion,
-	transaction_validity::{TransactionTag as Tag, ValidTransaction},
+	transaction_validity::{
+		TransactionSource, TransactionTag as Tag, TransactionValidity, TransactionValidityError,
+		ValidTransaction,
+	},
 };
 use std::time::Instant;
 use tracing::{debug, trace, warn};
@@ -41,6 +50,7 @@ use super::{
 		BlockHash, ChainApi, EventStream, ExtrinsicFor, ExtrinsicHash, Options, TransactionFor,
 	},
 	rotator::PoolRotator,
+	validation_cache::ValidationCache,
 	watcher::Watcher,
 };
 
@@ -112,6 +122,26 @@ impl From<Box<dyn Fn() -> bool + Send + Sync>> for IsValidator {
 	}
 }
 
+/// The final status of the transaction affected by [`ValidatedPool::resubmit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResubmitOutcome {
+	/// The transaction was reinstated in the future queue.
+	Future,
+	/// The transaction was reinstated in the ready queue.
+	Ready,
+	/// The transaction was invalid or could not be imported.
+	Failed,
+	/// The transaction was dropped from the pool.
+	Dropped,
+}
+
+impl ResubmitOutcome {
+	/// Returns true if the transaction is back in the pool.
+	pub fn is_reinstated(&self) -> bool {
+		matches!(self, Self::Future | Self::Ready)
+	}
+}
+
 /// Represents the result of `submit` or `submit_and_watch` operations.
 pub struct BaseSubmitOutcome<B: ChainApi, W> {
 	/// The hash of the submitted transaction.
@@ -165,6 +195,10 @@ pub struct ValidatedPool<B: ChainApi, L: EventHandler<B>> {
 	pub(crate) pool: RwLock<base::BasePool<ExtrinsicHash<B>, ExtrinsicFor<B>>>,
 	import_notification_sinks: Mutex<Vec<Sender<ExtrinsicHash<B>>>>,
 	rotator: PoolRotator<ExtrinsicHash<B>>,
+	validation_cache: Option<ValidationCache<ExtrinsicHash<B>>>,
+	/// The number of timed out validations since the last call to
+	/// [`ValidatedPool::take_validation_timeouts`].
+	validation_timeouts: AtomicU64,
 }
 
 impl<B: ChainApi, L: EventHandler<B>> Clone for ValidatedPool<B, L> {
@@ -177,6 +211,8 @@ impl<B: ChainApi, L: EventHandler<B>> Clone for ValidatedPool<B, L> {
 			pool: RwLock::from(self.pool.read().clone()),
 			import_notification_sinks: Default::default(),
 			rotator: self.rotator.clone(),
+			validation_cache: self.validation_cache.clone(),
+			validation_timeouts: AtomicU64::new(0),
 		}
 	}
 }
@@ -240,6 +276,7 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		event_handler: Option<L>,
 	) -> Self {
 		let base_pool = base::BasePool::new(options.reject_future_transactions);
+		let validation_cache = options.validation_cache_size.map(ValidationCache::new);
 		Self {
 			is_validator,
 			options,
@@ -248,6 +285,8 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 			pool: RwLock::new(base_pool),
 			import_notification_sinks: Default::default(),
 			rotator,
+			validation_cache,
+			validation_timeouts: AtomicU64::new(0),
 		}
 	}
 
@@ -256,6 +295,11 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		self.rotator.ban(now, hashes)
 	}
 
+	/// Lifts the ban of given set of hashes.
+	pub fn unban<'a>(&self, hashes: impl IntoIterator<Item = &'a ExtrinsicHash<B>>) {
+		self.rotator.unban(hashes)
+	}
+
 	/// Returns true if transaction with given hash is currently banned from the pool.
 	pub fn is_banned(&self, hash: &ExtrinsicHash<B>) -> bool {
 		self.rotator.is_banned(hash)
@@ -307,6 +351,21 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 			.collect()
 	}
 
+	/// Imports a single pre-validated transaction to the pool.
+	///
+	/// Equivalent to [`Self::submit`] called with a single transaction, but avoids collecting the
+	/// results.
+	pub fn submit_single(
+		&self,
+		tx: ValidatedTransactionFor<B>,
+	) -> Result<ValidatedPoolSubmitOutcome<B>, B::Error> {
+		let outcome = self.submit_one(tx)?;
+		if self.enforce_limits().contains(&outcome.hash) {
+			return Err(error::Error::ImmediatelyDropped.into())
+		}
+		Ok(outcome)
+	}
+
 	/// Submit single pre-validated transaction to the pool.
 	fn submit_one(
 		&self,
@@ -429,9 +488,7 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 			ValidatedTransaction::Valid(tx) => {
 				let hash = self.api.hash_and_length(&tx.data).0;
 				let watcher = self.create_watcher(hash);
-				self.submit(std::iter::once(ValidatedTransaction::Valid(tx)))
-					.pop()
-					.expect("One extrinsic passed; one result returned; qed")
+				self.submit_single(ValidatedTransaction::Valid(tx))
 					.map(|outcome| outcome.with_watcher(watcher))
 			},
 			ValidatedTransaction::Invalid(hash, err) => {
@@ -459,17 +516,14 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 	///
 	/// Removes and then submits passed transactions and all dependent transactions.
 	/// Transactions that are missing from the pool are not submitted.
+	///
+	/// Returns the final outcome of every transaction affected by the resubmission, including the
+	/// dependent transactions.
 	pub fn resubmit(
 		&self,
 		mut updated_transactions: IndexMap<ExtrinsicHash<B>, ValidatedTransactionFor<B>>,
-	) {
-		#[derive(Debug, Clone, Copy, PartialEq)]
-		enum Status {
-			Future,
-			Ready,
-			Failed,
-			Dropped,
-		}
+	) -> HashMap<ExtrinsicHash<B>, ResubmitOutcome> {
+		use ResubmitOutcome as Status;
 
 		let (mut initial_statuses, final_statuses) = {
 			let mut pool = self.pool.write();
@@ -502,11 +556,9 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 					} else {
 						// in most cases we'll end up in successful `try_unwrap`, but if not
 						// we still need to reinsert transaction back to the pool => duplicate call
-						let transaction = match Arc::try_unwrap(removed_tx) {
-							Ok(transaction) => transaction,
-							Err(transaction) => transaction.duplicate(),
-						};
-						ValidatedTransaction::Valid(transaction)
+						ValidatedTransaction::Valid(base::Transaction::unwrap_or_duplicate(
+							removed_tx,
+						))
 					};
 
 					initial_statuses.insert(removed_hash, Status::Ready);
@@ -578,17 +630,18 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 
 		// and now let's notify listeners about status changes
 		let mut event_dispatcher = self.event_dispatcher.write();
-		for (hash, final_status) in final_statuses {
-			let initial_status = initial_statuses.remove(&hash);
-			if initial_status.is_none() || Some(final_status) != initial_status {
+		for (hash, final_status) in &final_statuses {
+			let initial_status = initial_statuses.remove(hash);
+			if initial_status.is_none() || Some(*final_status) != initial_status {
 				match final_status {
-					Status::Future => event_dispatcher.future(&hash),
-					Status::Ready => event_dispatcher.ready(&hash, None),
-					Status::Dropped => event_dispatcher.dropped(&hash),
-					Status::Failed => event_dispatcher.invalid(&hash),
+					Status::Future => event_dispatcher.future(hash),
+					Status::Ready => event_dispatcher.ready(hash, None),
+					Status::Dropped => event_dispatcher.dropped(hash),
+					Status::Failed => event_dispatcher.invalid(hash),
 				}
 			}
 		}
+		final_statuses
 	}
 
 	/// For each extrinsic, returns tags that it provides (if known), or None (if it is unknown).
@@ -603,6 +656,18 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 			.collect()
 	}
 
+	/// For each hash, returns the transaction (either ready or future) if it is in the pool.
+	pub fn by_hashes(&self, hashes: &[ExtrinsicHash<B>]) -> Vec<Option<TransactionFor<B>>> {
+		self.pool.read().by_hashes(hashes)
+	}
+
+	/// Refreshes the timestamp of the transaction with given hash to now.
+	///
+	/// Returns `false` if the transaction is not in the pool.
+	pub fn touch(&self, hash: &ExtrinsicHash<B>) -> bool {
+		self.pool.write().touch(hash)
+	}
+
 	/// Get ready transaction by hash
 	pub fn ready_by_hash(&self, hash: &ExtrinsicHash<B>) -> Option<TransactionFor<B>> {
 		self.pool.read().ready_by_hash(hash)
@@ -716,6 +781,103 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		&self.api
 	}
 
+	/// Validates the transaction at given block.
+	///
+	/// If the validation cache is enabled, the cached result is returned instead of calling into
+	/// the runtime. Refer to [`Self::validate_with_cache`] for the results being cached.
+	///
+	/// If [`Options::validation_timeout`] is set, the runtime call which does not finish in time is
+	/// abandoned and [`error::Error::ValidationTimeout`] is returned.
+	pub async fn validate_transaction(
+		&self,
+		tx_hash: ExtrinsicHash<B>,
+		at: BlockHash<B>,
+		source: TransactionSource,
+		xt: ExtrinsicFor<B>,
+	) -> Result<TransactionValidity, B::Error> {
+		self.validate_with_cache(
+			tx_hash,
+			at,
+			source,
+			self.validate_transaction_with_timeout(tx_hash, at, source, xt),
+		)
+		.await
+	}
+
+	/// Calls into the runtime to validate the transaction, bounded by
+	/// [`Options::validation_timeout`] (if set).
+	async fn validate_transaction_with_timeout(
+		&self,
+		tx_hash: ExtrinsicHash<B>,
+		at: BlockHash<B>,
+		source: TransactionSource,
+		xt: ExtrinsicFor<B>,
+	) -> Result<TransactionValidity, B::Error> {
+		let validation = self.api.validate_transaction(at, source, xt);
+		let Some(timeout) = self.options.validation_timeout else { return validation.await };
+		tokio::time::timeout(timeout, validation).await.unwrap_or_else(|_| {
+			self.validation_timeouts.fetch_add(1, Ordering::Relaxed);
+			debug!(target: LOG_TARGET, ?tx_hash, ?at, ?timeout, "Transaction validation timed out");
+			Err(error::Error::ValidationTimeout.into())
+		})
+	}
+
+	/// Synchronously validates the transaction at given block.
+	///
+	/// Same as [`Self::validate_transaction`] but blocks the current thread when performing the
+	/// validation.
+	pub fn validate_transaction_blocking(
+		&self,
+		tx_hash: ExtrinsicHash<B>,
+		at: BlockHash<B>,
+		source: TransactionSource,
+		xt: ExtrinsicFor<B>,
+	) -> Result<TransactionValidity, B::Error> {
+		self.validate_with_cache(tx_hash, at, source, async {
+			self.api.validate_transaction_blocking(at, source, xt)
+		})
+		.now_or_never()
+		.expect("the validation future does not await anything and completes on the first poll")
+	}
+
+	/// Returns the validity of the transaction from the validation cache (if enabled), otherwise
+	/// awaits the `validation` future.
+	///
+	/// Only the final results of the validation (valid or invalid transaction) are cached. The
+	/// unknown validity and the errors (e.g. the validation timeout) are not, as the result may
+	/// differ once the validation is retried.
+	async fn validate_with_cache(
+		&self,
+		tx_hash: ExtrinsicHash<B>,
+		at: BlockHash<B>,
+		source: TransactionSource,
+		validation: impl Future<Output = Result<TransactionValidity, B::Error>>,
+	) -> Result<TransactionValidity, B::Error> {
+		let Some(cache) = &self.validation_cache else { return validation.await };
+		if let Some(validity) = cache.get(tx_hash, at, source) {
+			return Ok(validity)
+		}
+		let validity = validation.await?;
+		if !matches!(validity, Err(TransactionValidityError::Unknown(_))) {
+			cache.insert(tx_hash, at, source, validity.clone());
+		}
+		Ok(validity)
+	}
+
+	/// Returns the number of timed out validations since the last call and resets the counter.
+	///
+	/// Refer to [`Options::validation_timeout`] for more details.
+	pub fn take_validation_timeouts(&self) -> u64 {
+		self.validation_timeouts.swap(0, Ordering::Relaxed)
+	}
+
+	/// Returns the number of validation cache hits since the last call and resets the counter.
+	///
+	/// Always returns zero if the validation cache is disabled.
+	pub fn take_validation_cache_hits(&self) -> u64 {
+		self.validation_cache.as_ref().map_or(0, |cache| cache.take_hits())
+	}
+
 	/// Return an event stream of notifications for when transactions are imported to the pool.
 	///
 	/// Consumers of this stream should use the `ready` method to actually get the
@@ -768,6 +930,76 @@ impl<B: ChainApi, L: EventHandler<B>> ValidatedPool<B, L> {
 		invalid
 	}
 
+	/// Removes transactions of unknown validity from the pool.
+	///
+	/// Unlike [`Self::remove_invalid`], the transactions are not banned, as they may become valid
+	/// later. For every removed transaction a Dropped event is triggered.
+	///
+	/// Returns the list of actually removed transactions, which may include transactions dependent
+	/// on provided set.
+	pub fn remove_unknown(&self, hashes: &[ExtrinsicHash<B>]) -> Vec<TransactionFor<B>> {
+		if hashes.is_empty() {
+			return vec![]
+		}
+
+		let removed = self.remove_subtree(hashes, false, |listener, removed_tx_hash| {
+			listener.dropped(&removed_tx_hash);
+		});
+
+		trace!(
+			target: LOG_TARGET,
+			removed_count = hashes.len(),
+			unknown_count = removed.len(),
+			"Removed transactions of unknown validity"
+		);
+		log_xt_trace!(
+			target: LOG_TARGET,
+			removed.iter().map(|t| t.hash),
+			"Removed transaction of unknown validity"
+		);
+
+		removed
+	}
+
+	/// Removes a single transaction from the pool, keeping the transactions depending on it.
+	///
+	/// The removed transaction is not banned and no event is triggered for it. The ready
+	/// dependents are moved to the future queue and a Future event is triggered for each of them.
+	///
+	/// Returns the removed transaction, if it was present in the pool.
+	pub fn remove_single(&self, hash: &ExtrinsicHash<B>) -> Option<TransactionFor<B>> {
+		let (removed, demoted) = self.pool.write().remove_single(hash);
+
+		trace!(
+			target: LOG_TARGET,
+			tx_hash = ?hash,
+			found = removed.is_some(),
+			demoted_count = demoted.len(),
+			"Removed single transaction"
+		);
+
+		let mut event_dispatcher = self.event_dispatcher.write();
+		demoted.iter().for_each(|hash| event_dispatcher.future(hash));
+
+		removed
+	}
+
+	/// Invokes the `event_dispatcher_action` callback with the pool's event dispatcher.
+	///
+	/// Allows to trigger the events for transactions manipulated outside of the regular
+	/// submission and removal paths, once the outcome of such manipulation is known.
+	pub fn dispatch_events<F>(&self, event_dispatcher_action: F)
+	where
+		F: FnOnce(&mut EventDispatcher<B, L>),
+	{
+		event_dispatcher_action(&mut *self.event_dispatcher.write());
+	}
+
+	/// Returns a reference to the pool configuration options.
+	pub fn options(&self) -> &Options {
+		&self.options
+	}
+
 	/// Get an iterator for ready transactions ordered by priority
 	pub fn ready(&self) -> impl ReadyTransactions<Item = TransactionFor<B>> + Send {
 		self.pool.read().ready()
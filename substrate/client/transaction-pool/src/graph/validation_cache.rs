@@ -0,0 +1,159 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Cache of the transaction validation results.
+//!
+//! Keeps the most recently used results of the runtime validation, keyed by the transaction hash
+//! and the hash of the block the transaction was validated at. The state of the block is fixed, so
+//! the cached result can be reused instead of calling into the runtime again.
+
+use linked_hash_map::LinkedHashMap;
+use parking_lot::Mutex;
+use sp_runtime::transaction_validity::{TransactionSource, TransactionValidity};
+use std::{
+	hash,
+	sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Least recently used cache of the transaction validation results.
+///
+/// The validity of transaction may depend on its source, so the cached result is only reused if
+/// the transaction is validated with the same source.
+pub struct ValidationCache<Hash> {
+	/// The maximal number of cached results.
+	capacity: usize,
+	/// Cached results, ordered from the least to the most recently used one.
+	entries: Mutex<LinkedHashMap<(Hash, Hash), (TransactionSource, TransactionValidity)>>,
+	/// The number of cache hits since the last call to [`ValidationCache::take_hits`].
+	hits: AtomicU64,
+}
+
+impl<Hash: hash::Hash + Eq + Clone> Clone for ValidationCache<Hash> {
+	fn clone(&self) -> Self {
+		Self {
+			capacity: self.capacity,
+			entries: Mutex::new(self.entries.lock().clone()),
+			hits: AtomicU64::new(0),
+		}
+	}
+}
+
+impl<Hash: hash::Hash + Eq> ValidationCache<Hash> {
+	/// New cache instance holding at most `capacity` results.
+	pub fn new(capacity: usize) -> Self {
+		Self { capacity, entries: Default::default(), hits: AtomicU64::new(0) }
+	}
+
+	/// Returns the cached result of validation of given transaction at given block.
+	///
+	/// The result is only returned if the transaction was validated with the same `source`.
+	pub fn get(
+		&self,
+		tx_hash: Hash,
+		at: Hash,
+		source: TransactionSource,
+	) -> Option<TransactionValidity> {
+		let mut entries = self.entries.lock();
+		let (cached_source, validity) = entries.get_refresh(&(tx_hash, at))?;
+		if *cached_source != source {
+			return None
+		}
+		self.hits.fetch_add(1, Ordering::Relaxed);
+		Some(validity.clone())
+	}
+
+	/// Caches the result of validation of given transaction at given block.
+	///
+	/// The least recently used results are evicted if the capacity is exceeded.
+	pub fn insert(
+		&self,
+		tx_hash: Hash,
+		at: Hash,
+		source: TransactionSource,
+		validity: TransactionValidity,
+	) {
+		let mut entries = self.entries.lock();
+		entries.insert((tx_hash, at), (source, validity));
+		while entries.len() > self.capacity {
+			entries.pop_front();
+		}
+	}
+
+	/// Returns the number of cache hits since the last call and resets the counter.
+	pub fn take_hits(&self) -> u64 {
+		self.hits.swap(0, Ordering::Relaxed)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::transaction_validity::{InvalidTransaction, ValidTransaction};
+
+	type Hash = u64;
+
+	const SOURCE: TransactionSource = TransactionSource::External;
+
+	fn valid(priority: u64) -> TransactionValidity {
+		Ok(ValidTransaction { priority, ..Default::default() })
+	}
+
+	#[test]
+	fn should_return_cached_result() {
+		// given
+		let cache = ValidationCache::<Hash>::new(4);
+		cache.insert(1, 10, SOURCE, valid(1));
+		cache.insert(2, 10, SOURCE, Err(InvalidTransaction::Stale.into()));
+
+		// then
+		assert_eq!(cache.get(1, 10, SOURCE), Some(valid(1)));
+		assert_eq!(cache.get(2, 10, SOURCE), Some(Err(InvalidTransaction::Stale.into())));
+		assert_eq!(cache.get(1, 11, SOURCE), None);
+		assert_eq!(cache.get(3, 10, SOURCE), None);
+		assert_eq!(cache.take_hits(), 2);
+		assert_eq!(cache.take_hits(), 0);
+	}
+
+	#[test]
+	fn should_not_return_result_cached_for_other_source() {
+		// given
+		let cache = ValidationCache::<Hash>::new(4);
+		cache.insert(1, 10, SOURCE, valid(1));
+
+		// then
+		assert_eq!(cache.get(1, 10, TransactionSource::Local), None);
+		assert_eq!(cache.take_hits(), 0);
+	}
+
+	#[test]
+	fn should_evict_least_recently_used_result() {
+		// given
+		let cache = ValidationCache::<Hash>::new(2);
+		cache.insert(1, 10, SOURCE, valid(1));
+		cache.insert(2, 10, SOURCE, valid(2));
+
+		// when
+		assert!(cache.get(1, 10, SOURCE).is_some());
+		cache.insert(3, 10, SOURCE, valid(3));
+
+		// then
+		assert_eq!(cache.get(1, 10, SOURCE), Some(valid(1)));
+		assert_eq!(cache.get(2, 10, SOURCE), None);
+		assert_eq!(cache.get(3, 10, SOURCE), Some(valid(3)));
+	}
+}
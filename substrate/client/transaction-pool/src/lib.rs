@@ -35,7 +35,10 @@ use std::sync::Arc;
 pub use api::FullChainApi;
 pub use builder::{Builder, TransactionPoolHandle, TransactionPoolOptions, TransactionPoolType};
 pub use common::notification_future;
-pub use fork_aware_txpool::{ForkAwareTxPool, ForkAwareTxPoolTask};
+pub use fork_aware_txpool::{
+	ForkAwareTxPool, ForkAwareTxPoolTask, ImportState, PreValidationFilter, ReplaceOutcome,
+	RevalidationRun, StagedOutcome, ValidationTracer, ViewPin, ViewStats,
+};
 pub use graph::{
 	base_pool::{Limit as PoolLimit, TimedTransactionSource},
 	ChainApi, Options, Pool,
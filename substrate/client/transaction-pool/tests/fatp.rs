@@ -19,18 +19,28 @@
 //! Tests for fork-aware transaction pool.
 
 use fatp_common::{
-	finalized_block_event, invalid_hash, new_best_block_event, pool, pool_with_api,
+	build_and_hash, finalized_block_event, invalid_hash, new_best_block_event, pool, pool_with_api,
 	test_chain_with_forks, LOG_TARGET, SOURCE,
 };
 use futures::{executor::block_on, task::Poll, FutureExt, StreamExt};
-use sc_transaction_pool::ChainApi;
+use sc_transaction_pool::{ChainApi, ImportState, RevalidationRun};
 use sc_transaction_pool_api::{
 	error::Error as TxPoolError, ChainEvent, MaintainedTransactionPool, TransactionPool,
 	TransactionStatus,
 };
-use sp_runtime::transaction_validity::InvalidTransaction;
-use std::{sync::Arc, time::Duration};
-use substrate_test_runtime_client::Sr25519Keyring::*;
+use sp_runtime::{
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	Weight,
+};
+use std::{
+	collections::HashSet,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+use substrate_test_runtime_client::{
+	runtime::{substrate_test_pallet, ExtrinsicBuilder, RuntimeCall, TransferData},
+	Sr25519Keyring::*,
+};
 use substrate_test_runtime_transaction_pool::uxt;
 use tracing::debug;
 
@@ -71,6 +81,1076 @@ fn fatp_no_view_future_and_ready_submit_one_works() {
 	assert!(results.iter().all(|r| { r.is_ok() }));
 }
 
+#[test]
+fn fatp_build_and_hash_matches_submitted_hash() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let (xt0, xt0_hash) = build_and_hash(ExtrinsicBuilder::new_include_data(vec![1, 2, 3]), &*api);
+
+	let result = block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone()));
+	assert_eq!(result.unwrap(), xt0_hash);
+	assert_eq!(xt0_hash, api.hash_and_length(&xt0).0);
+}
+
+#[test]
+fn fatp_view_stats_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Bob, 202);
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap();
+
+	let stats = pool.view_stats_at(header01.hash()).unwrap();
+	assert_eq!(stats.ready, 1);
+	assert_eq!(stats.future, 1);
+	assert_eq!(stats.total_bytes, api.hash_and_length(&xt0).1 + api.hash_and_length(&xt1).1);
+	assert!(stats.oldest_tx_age.is_some());
+	assert!(!stats.revalidation_in_progress);
+
+	assert!(pool.view_stats_at(invalid_hash()).is_none());
+}
+
+#[test]
+fn fatp_view_stats_reports_clone_depth() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+	assert_eq!(pool.view_stats_at(header01.hash()).unwrap().clone_depth, 0);
+
+	let header02 = api.push_block_with_parent(header01.hash(), vec![], true);
+	let event = new_best_block_event(&pool, Some(header01.hash()), header02.hash());
+	block_on(pool.maintain(event));
+	assert_eq!(pool.view_stats_at(header02.hash()).unwrap().clone_depth, 1);
+
+	let header03 = api.push_block_with_parent(header02.hash(), vec![], true);
+	let event = new_best_block_event(&pool, Some(header02.hash()), header03.hash());
+	block_on(pool.maintain(event));
+	assert_eq!(pool.view_stats_at(header03.hash()).unwrap().clone_depth, 2);
+}
+
+#[test]
+fn fatp_force_remove_keeps_dependents() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Alice, 202);
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt2.clone())).unwrap();
+	assert_pool_status!(header01.hash(), &pool, 3, 0);
+
+	assert!(pool.force_remove_at(header01.hash(), &api.hash_and_length(&xt1).0));
+	assert_pool_status!(header01.hash(), &pool, 1, 1);
+	assert_eq!(pool.mempool_len(), (2, 0));
+	assert_ready_iterator!(header01.hash(), pool, [xt0]);
+	assert_future_iterator!(header01.hash(), pool, [xt2]);
+
+	assert!(!pool.force_remove_at(header01.hash(), &api.hash_and_length(&xt1).0));
+	assert!(!pool.force_remove_at(invalid_hash(), &api.hash_and_length(&xt0).0));
+}
+
+#[test]
+fn fatp_evict_oldest_removes_subtrees() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Bob, 200);
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	std::thread::sleep(Duration::from_millis(10));
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt2.clone())).unwrap();
+	std::thread::sleep(Duration::from_millis(10));
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap();
+	assert_pool_status!(header01.hash(), &pool, 2, 1);
+
+	let evicted = pool.evict_oldest_at(header01.hash(), 1).unwrap();
+	assert_eq!(
+		evicted.into_iter().collect::<HashSet<_>>(),
+		HashSet::from([api.hash_and_length(&xt0).0, api.hash_and_length(&xt1).0])
+	);
+	assert_pool_status!(header01.hash(), &pool, 0, 1);
+	assert_future_iterator!(header01.hash(), pool, [xt2]);
+	assert_eq!(pool.mempool_len(), (1, 0));
+
+	assert_eq!(
+		pool.evict_oldest_at(header01.hash(), 10).unwrap(),
+		vec![api.hash_and_length(&xt2).0]
+	);
+	assert_pool_status!(header01.hash(), &pool, 0, 0);
+	assert_eq!(pool.mempool_len(), (0, 0));
+	assert_eq!(pool.evict_oldest_at(header01.hash(), 10).unwrap(), vec![]);
+	assert!(pool.evict_oldest_at(invalid_hash(), 1).is_none());
+}
+
+#[test]
+fn fatp_evict_by_sender_removes_and_bans_subtrees() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+	api.set_nonce(header01.hash(), Bob.into(), 300);
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Alice, 203);
+	let xt3 = uxt(Bob, 300);
+
+	for xt in [&xt0, &xt1, &xt2, &xt3] {
+		block_on(pool.submit_one(header01.hash(), SOURCE, xt.clone())).unwrap();
+	}
+	assert_pool_status!(header01.hash(), &pool, 3, 1);
+
+	let extract = |xt: &Arc<_>| TransferData::try_from(&**xt).ok().map(|transfer| transfer.from);
+	let evicted = pool.evict_by_sender_at(header01.hash(), extract, &Alice.into()).unwrap();
+	assert_eq!(
+		evicted.into_iter().collect::<HashSet<_>>(),
+		[&xt0, &xt1, &xt2].into_iter().map(|xt| api.hash_and_length(xt).0).collect()
+	);
+	assert_pool_status!(header01.hash(), &pool, 1, 0);
+	assert_ready_iterator!(header01.hash(), pool, [xt3]);
+	// the evicted transactions are removed from the mempool
+	assert_eq!(pool.mempool_len(), (1, 0));
+
+	// the evicted transactions are banned
+	let result = block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone()));
+	assert!(matches!(result.unwrap_err().0, TxPoolError::TemporarilyBanned));
+
+	assert_eq!(pool.evict_by_sender_at(header01.hash(), extract, &Alice.into()).unwrap(), vec![]);
+	assert!(pool.evict_by_sender_at(invalid_hash(), extract, &Alice.into()).is_none());
+}
+
+#[test]
+fn fatp_clear_removes_all_transactions() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Alice, 203);
+
+	let xts = vec![xt0.clone(), xt1.clone(), xt2.clone()];
+	let results = block_on(pool.submit_at(header01.hash(), SOURCE, xts.clone())).unwrap();
+	assert!(results.iter().all(Result::is_ok));
+	assert_pool_status!(header01.hash(), &pool, 2, 1);
+	assert_eq!(pool.mempool_len(), (3, 0));
+
+	let cleared = pool.clear_at(header01.hash()).unwrap();
+	assert_eq!(
+		cleared.into_iter().collect::<HashSet<_>>(),
+		xts.iter().map(|xt| api.hash_and_length(xt).0).collect::<HashSet<_>>()
+	);
+	assert_pool_status!(header01.hash(), &pool, 0, 0);
+	assert_eq!(pool.mempool_len(), (0, 0));
+	assert_eq!(pool.clear_at(header01.hash()).unwrap(), vec![]);
+	assert!(pool.clear_at(invalid_hash()).is_none());
+
+	// the view is kept alive
+	let xt3 = uxt(Bob, 300);
+	api.set_nonce(header01.hash(), Bob.into(), 300);
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt3.clone())).unwrap();
+	assert_pool_status!(header01.hash(), &pool, 1, 0);
+	assert_ready_iterator!(header01.hash(), pool, [xt3]);
+}
+
+#[test]
+fn fatp_pre_validation_filter_rejects_transactions() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+	let bob = Bob.into();
+	let pool =
+		pool.with_pre_validation_filter(Arc::new(move |xt| match TransferData::try_from(&**xt) {
+			Ok(transfer) if transfer.from == bob =>
+				Err(TxPoolError::InvalidTransaction(InvalidTransaction::Call)),
+			_ => Ok(()),
+		}));
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Bob, 200);
+	let xt2 = uxt(Alice, 201);
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	let result = block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone()));
+	assert!(matches!(
+		result.unwrap_err().0,
+		TxPoolError::InvalidTransaction(InvalidTransaction::Call)
+	));
+
+	let results =
+		block_on(pool.submit_at(header01.hash(), SOURCE, vec![xt1.clone(), xt2.clone()])).unwrap();
+	assert!(matches!(
+		results[0].as_ref().unwrap_err().0,
+		TxPoolError::InvalidTransaction(InvalidTransaction::Call)
+	));
+	assert_eq!(*results[1].as_ref().unwrap(), api.hash_and_length(&xt2).0);
+
+	assert_pool_status!(header01.hash(), &pool, 2, 0);
+	assert_ready_iterator!(header01.hash(), pool, [xt0, xt2]);
+}
+
+#[test]
+fn fatp_call_filter_rejects_disallowed_calls() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+	let pool = pool.with_call_filter(|xt| {
+		!matches!(
+			xt.function,
+			RuntimeCall::SubstrateTest(substrate_test_pallet::Call::include_data { .. })
+		)
+	});
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = ExtrinsicBuilder::new_include_data(vec![1, 2, 3]).build();
+
+	let results =
+		block_on(pool.submit_at(header01.hash(), SOURCE, vec![xt0.clone(), xt1.clone()])).unwrap();
+	assert_eq!(*results[0].as_ref().unwrap(), api.hash_and_length(&xt0).0);
+	assert!(matches!(results[1].as_ref().unwrap_err().0, TxPoolError::Filtered));
+
+	let result = block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone()));
+	assert!(matches!(result.unwrap_err().0, TxPoolError::Filtered));
+
+	assert_pool_status!(header01.hash(), &pool, 1, 0);
+	assert_ready_iterator!(header01.hash(), pool, [xt0]);
+}
+
+#[test]
+fn fatp_submit_many_atomic_rolls_back_on_failure() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Alice, 199);
+	let xt3 = uxt(Alice, 203);
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt3.clone())).unwrap();
+	assert_pool_status!(header01.hash(), &pool, 0, 1);
+
+	let result = block_on(pool.submit_many_atomic_at(
+		header01.hash(),
+		SOURCE,
+		vec![xt0.clone(), xt1.clone(), xt2.clone()],
+	));
+	let (index, error) = result.unwrap().unwrap_err();
+	assert_eq!(index, 2);
+	assert!(matches!(error.0, TxPoolError::InvalidTransaction(InvalidTransaction::Stale)));
+	assert_pool_status!(header01.hash(), &pool, 0, 1);
+	assert_future_iterator!(header01.hash(), pool, [xt3]);
+	assert_eq!(pool.mempool_len(), (1, 0));
+
+	let xts = vec![xt0.clone(), xt1.clone()];
+	let hashes = block_on(pool.submit_many_atomic_at(header01.hash(), SOURCE, xts))
+		.unwrap()
+		.unwrap();
+	assert_eq!(hashes, vec![api.hash_and_length(&xt0).0, api.hash_and_length(&xt1).0]);
+	assert_pool_status!(header01.hash(), &pool, 2, 1);
+	assert_eq!(pool.mempool_len(), (3, 0));
+	assert!(block_on(pool.submit_many_atomic_at(invalid_hash(), SOURCE, vec![xt0])).is_none());
+}
+
+#[test]
+fn fatp_drain_view_into_moves_transactions() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let header01b = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, Some(header01.hash()), header01b.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Bob, 300);
+	let xt3 = uxt(Alice, 203);
+	api.set_nonce(header01.hash(), Bob.into(), 300);
+	let xts = vec![xt0.clone(), xt1.clone(), xt2.clone(), xt3.clone()];
+	block_on(pool.submit_many_atomic_at(header01.hash(), SOURCE, xts))
+		.unwrap()
+		.unwrap();
+	assert_pool_status!(header01.hash(), &pool, 3, 1);
+	assert_pool_status!(header01b.hash(), &pool, 0, 0);
+	assert_eq!(pool.mempool_len(), (4, 0));
+
+	api.add_invalid(&xt2);
+	let results = block_on(pool.drain_view_into(header01.hash(), header01b.hash())).unwrap();
+	assert_eq!(results.len(), 4);
+
+	let position =
+		|xt| results.iter().position(|(hash, _)| *hash == api.hash_and_length(xt).0).unwrap();
+	assert!(position(&xt0) < position(&xt1));
+	assert_eq!(position(&xt3), 3);
+	for (hash, result) in &results {
+		assert_eq!(result.is_ok(), *hash != api.hash_and_length(&xt2).0);
+	}
+
+	assert_pool_status!(header01.hash(), &pool, 0, 0);
+	assert_pool_status!(header01b.hash(), &pool, 2, 1);
+	assert_ready_iterator!(header01b.hash(), pool, [xt0, xt1]);
+	assert_eq!(pool.mempool_len(), (3, 0));
+	assert!(block_on(pool.drain_view_into(invalid_hash(), header01b.hash())).is_none());
+}
+
+#[test]
+fn fatp_transactions_by_hashes_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 202);
+	let xt2 = uxt(Alice, 203);
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap();
+	assert_pool_status!(header01.hash(), &pool, 1, 1);
+
+	let hashes = [xt0, xt1, xt2].map(|xt| api.hash_and_length(&xt).0);
+	let txs = pool.transactions_by_hashes_at(header01.hash(), &hashes).unwrap();
+	assert_eq!(txs.len(), 3);
+	assert_eq!(txs[0].as_ref().map(|tx| tx.hash), Some(hashes[0]));
+	assert_eq!(txs[1].as_ref().map(|tx| tx.hash), Some(hashes[1]));
+	assert!(txs[2].is_none());
+
+	assert_eq!(pool.is_imported_at(header01.hash(), &hashes[1]), Some(true));
+	assert_eq!(pool.is_imported_at(header01.hash(), &hashes[2]), Some(false));
+	assert!(pool.transactions_by_hashes_at(invalid_hash(), &hashes).is_none());
+	assert!(pool.is_imported_at(invalid_hash(), &hashes[0]).is_none());
+}
+
+#[test]
+fn fatp_import_state_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 202);
+	let xt2 = uxt(Bob, 300);
+	let xt3 = uxt(Charlie, 400);
+	api.add_invalid(&xt2);
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt2.clone())).unwrap_err();
+
+	let state = |xt| pool.import_state_at(header01.hash(), &api.hash_and_length(xt).0);
+	assert_eq!(state(&xt0), Some(ImportState::Ready));
+	assert_eq!(state(&xt1), Some(ImportState::Future));
+	assert_eq!(state(&xt2), Some(ImportState::Banned));
+	assert_eq!(state(&xt3), Some(ImportState::Unknown));
+	assert_eq!(pool.is_imported_at(header01.hash(), &api.hash_and_length(&xt2).0), Some(true));
+	assert_eq!(pool.is_imported_at(header01.hash(), &api.hash_and_length(&xt3).0), Some(false));
+	assert!(pool.import_state_at(invalid_hash(), &api.hash_and_length(&xt0).0).is_none());
+}
+
+#[test]
+fn fatp_touch_refreshes_transaction_timestamp() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 202);
+	let xt2 = uxt(Alice, 203);
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap();
+	assert_pool_status!(header01.hash(), &pool, 1, 1);
+
+	let hashes = [xt0, xt1, xt2].map(|xt| api.hash_and_length(&xt).0);
+	let timestamps = || {
+		pool.transactions_by_hashes_at(header01.hash(), &hashes[..2])
+			.unwrap()
+			.into_iter()
+			.map(|tx| tx.unwrap().source.timestamp.unwrap())
+			.collect::<Vec<_>>()
+	};
+	let before = timestamps();
+
+	assert_eq!(pool.touch_at(header01.hash(), &hashes[0]), Some(true));
+	assert_eq!(pool.touch_at(header01.hash(), &hashes[1]), Some(true));
+	assert_eq!(pool.touch_at(header01.hash(), &hashes[2]), Some(false));
+	assert!(pool.touch_at(invalid_hash(), &hashes[0]).is_none());
+
+	let after = timestamps();
+	assert!(after.iter().zip(before.iter()).all(|(after, before)| after > before));
+	assert_pool_status!(header01.hash(), &pool, 1, 1);
+}
+
+#[test]
+fn fatp_pinned_view_survives_finalization() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+
+	let header02 = api.push_block(2, vec![], true);
+	let event = new_best_block_event(&pool, Some(header01.hash()), header02.hash());
+	block_on(pool.maintain(event));
+	assert_eq!(pool.active_views_count(), 1);
+	assert_eq!(pool.inactive_views_count(), 1);
+
+	let pin = pool.pin_view_at(header01.hash()).unwrap();
+	assert!(pool.pin_view_at(invalid_hash()).is_none());
+
+	let header03 = api.push_block(3, vec![], true);
+	let event = finalized_block_event(&pool, header02.hash(), header03.hash());
+	block_on(pool.maintain(event));
+
+	assert_eq!(pool.active_views_count(), 1);
+	assert_eq!(pool.inactive_views_count(), 1);
+	assert_eq!(pool.view_stats_at(header01.hash()).unwrap().ready, 1);
+	assert!(pool.view_stats_at(header02.hash()).is_none());
+
+	drop(pin);
+
+	let header04 = api.push_block(4, vec![], true);
+	let event = finalized_block_event(&pool, header03.hash(), header04.hash());
+	block_on(pool.maintain(event));
+
+	assert_eq!(pool.active_views_count(), 1);
+	assert_eq!(pool.inactive_views_count(), 0);
+	assert!(pool.view_stats_at(header01.hash()).is_none());
+}
+
+#[test]
+fn fatp_ready_hashes_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Alice, 203);
+
+	for xt in [&xt0, &xt1, &xt2] {
+		block_on(pool.submit_one(header01.hash(), SOURCE, xt.clone())).unwrap();
+	}
+	assert_pool_status!(header01.hash(), &pool, 2, 1);
+
+	let ready_hashes = pool
+		.ready_hashes_at(header01.hash())
+		.unwrap()
+		.into_iter()
+		.collect::<HashSet<_>>();
+	assert_eq!(
+		ready_hashes,
+		HashSet::from([api.hash_and_length(&xt0).0, api.hash_and_length(&xt1).0])
+	);
+	assert!(pool.ready_hashes_at(invalid_hash()).is_none());
+}
+
+#[test]
+fn fatp_ready_since_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Alice, 202);
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	std::thread::sleep(Duration::from_millis(10));
+	let since = Instant::now();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt2.clone())).unwrap();
+
+	assert_eq!(
+		pool.ready_since_at(header01.hash(), since).unwrap(),
+		vec![api.hash_and_length(&xt1).0, api.hash_and_length(&xt2).0]
+	);
+	assert_eq!(pool.ready_since_at(header01.hash(), Instant::now()).unwrap(), vec![]);
+	assert!(pool.ready_since_at(invalid_hash(), since).is_none());
+}
+
+#[test]
+fn fatp_ready_iter_limited_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xts = vec![uxt(Alice, 200), uxt(Alice, 201), uxt(Alice, 202)];
+	for xt in &xts {
+		block_on(pool.submit_one(header01.hash(), SOURCE, xt.clone())).unwrap();
+	}
+	assert_pool_status!(header01.hash(), &pool, 3, 0);
+
+	let limited = |n| {
+		pool.ready_iter_limited_at(header01.hash(), n)
+			.unwrap()
+			.into_iter()
+			.map(|tx| tx.hash)
+			.collect::<Vec<_>>()
+	};
+	let hashes = xts.iter().map(|xt| api.hash_and_length(xt).0).collect::<Vec<_>>();
+	assert_eq!(limited(0), vec![]);
+	assert_eq!(limited(2), hashes[..2].to_vec());
+	assert_eq!(limited(10), hashes);
+	assert!(pool.ready_iter_limited_at(invalid_hash(), 2).is_none());
+}
+
+#[test]
+fn fatp_get_validity_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	api.set_priority(&xt1, 7);
+	for xt in [&xt0, &xt1] {
+		block_on(pool.submit_one(header01.hash(), SOURCE, xt.clone())).unwrap();
+	}
+
+	let expected =
+		block_on(api.validate_transaction(header01.hash(), SOURCE, Arc::from(xt1.clone())))
+			.unwrap()
+			.unwrap();
+	let xt1_hash = api.hash_and_length(&xt1).0;
+	let validity = pool.get_validity_at(header01.hash(), &xt1_hash).unwrap();
+	assert_eq!(validity, expected);
+	assert_eq!(validity.priority, 7);
+
+	assert!(pool.get_validity_at(header01.hash(), &invalid_hash()).is_none());
+	assert!(pool.get_validity_at(invalid_hash(), &xt1_hash).is_none());
+}
+
+#[test]
+fn fatp_stage_commit_and_discard_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+	let header02 = api.push_block_with_parent(header01.hash(), vec![], true);
+
+	let xt0 = uxt(Alice, 200);
+	let xt0_hash = api.hash_and_length(&xt0).0;
+	api.set_priority(&xt0, 7);
+
+	let staged = block_on(pool.stage_at(header01.hash(), header02.hash(), xt0.clone()))
+		.unwrap()
+		.unwrap();
+	assert_eq!((staged.hash, staged.validity.priority), (xt0_hash, 7));
+	assert_pool_status!(header01.hash(), &pool, 0, 0);
+
+	let result = block_on(pool.stage_at(header01.hash(), header02.hash(), xt0.clone())).unwrap();
+	assert!(matches!(result.unwrap_err().0, TxPoolError::AlreadyImported(_)));
+	// the transaction can only be staged at the child block
+	let result = block_on(pool.stage_at(header01.hash(), header01.hash(), uxt(Alice, 201)));
+	assert!(matches!(result.unwrap().unwrap_err().0, TxPoolError::InvalidBlockId(_)));
+
+	assert_eq!(pool.discard_staged_at(header01.hash()).unwrap(), vec![xt0_hash]);
+	assert_pool_status!(header01.hash(), &pool, 0, 0);
+	assert_eq!(pool.mempool_len(), (0, 0));
+	assert!(block_on(pool.commit_staged_at(header01.hash())).unwrap().is_empty());
+
+	block_on(pool.stage_at(header01.hash(), header02.hash(), xt0.clone()))
+		.unwrap()
+		.unwrap();
+	let results = block_on(pool.commit_staged_at(header01.hash())).unwrap();
+	assert_eq!(results.into_iter().map(Result::unwrap).collect::<Vec<_>>(), vec![xt0_hash]);
+	assert_pool_status!(header01.hash(), &pool, 1, 0);
+	// the committed transaction is kept in the mempool
+	assert_eq!(pool.mempool_len(), (1, 0));
+
+	assert!(block_on(pool.stage_at(invalid_hash(), header02.hash(), xt0)).is_none());
+	assert!(pool.discard_staged_at(invalid_hash()).is_none());
+}
+
+#[test]
+fn fatp_pending_extrinsics_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Bob, 300);
+	let xt3 = uxt(Alice, 203);
+	api.set_nonce(header01.hash(), Bob.into(), 300);
+	api.set_priority(&xt2, 10);
+	for xt in [&xt1, &xt0, &xt2, &xt3] {
+		block_on(pool.submit_one(header01.hash(), SOURCE, xt.clone())).unwrap();
+	}
+	assert_pool_status!(header01.hash(), &pool, 3, 1);
+
+	let pending = pool
+		.pending_extrinsics_at(header01.hash())
+		.unwrap()
+		.into_iter()
+		.map(|xt| (*xt).clone())
+		.collect::<Vec<_>>();
+	assert_eq!(pending, vec![xt2, xt0, xt1]);
+	assert!(pool.pending_extrinsics_at(invalid_hash()).is_none());
+}
+
+#[test]
+fn fatp_ready_with_sources_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	block_on(pool.submit_one(header01.hash(), TransactionSource::Local, xt0.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap();
+
+	let ready = pool
+		.ready_with_sources_at(header01.hash())
+		.unwrap()
+		.map(|(tx, source)| (tx.hash, source.source))
+		.collect::<Vec<_>>();
+	assert_eq!(
+		ready,
+		vec![
+			(api.hash_and_length(&xt0).0, TransactionSource::Local),
+			(api.hash_and_length(&xt1).0, SOURCE),
+		]
+	);
+	assert!(pool.ready_with_sources_at(invalid_hash()).is_none());
+}
+
+#[test]
+fn fatp_subscribe_status_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let mut status = pool.subscribe_status_at(header01.hash()).unwrap();
+	assert!(!status.has_changed().unwrap());
+	assert!(status.borrow().is_empty());
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 202);
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap();
+
+	// both submissions are observed as a single transition
+	assert!(status.has_changed().unwrap());
+	let current = status.borrow_and_update().clone();
+	assert_eq!((current.ready, current.future), (1, 1));
+	assert!(!status.has_changed().unwrap());
+
+	// rejected submission does not change the status
+	let _ = block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone()));
+	assert!(!status.has_changed().unwrap());
+
+	pool.clear_at(header01.hash()).unwrap();
+	assert!(status.has_changed().unwrap());
+	assert!(status.borrow_and_update().is_empty());
+	assert!(pool.subscribe_status_at(invalid_hash()).is_none());
+}
+
+#[test]
+fn fatp_revalidate_with_budget_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Bob, 300);
+	let xt2 = uxt(Charlie, 400);
+	api.set_nonce(header01.hash(), Bob.into(), 300);
+	api.set_nonce(header01.hash(), Charlie.into(), 400);
+	for xt in [&xt0, &xt1, &xt2] {
+		block_on(pool.submit_one(header01.hash(), SOURCE, xt.clone())).unwrap();
+	}
+	assert_pool_status!(header01.hash(), &pool, 3, 0);
+
+	let run = block_on(pool.revalidate_with_budget_at(header01.hash(), 2)).unwrap();
+	assert_eq!(run, RevalidationRun { validated: 2, invalid: 0, remaining_budget: 0 });
+	assert_pool_status!(header01.hash(), &pool, 3, 0);
+
+	api.add_invalid(&xt1);
+	let run = block_on(pool.revalidate_with_budget_at(header01.hash(), 5)).unwrap();
+	assert_eq!(run, RevalidationRun { validated: 3, invalid: 1, remaining_budget: 2 });
+	assert_pool_status!(header01.hash(), &pool, 2, 0);
+	assert!(block_on(pool.revalidate_with_budget_at(invalid_hash(), 5)).is_none());
+}
+
+#[test]
+fn fatp_submit_many_stream_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 199);
+	let xt2 = uxt(Alice, 201);
+
+	let results = block_on(
+		pool.submit_many_stream_at(header01.hash(), SOURCE, vec![xt0.clone(), xt1, xt2.clone()])
+			.unwrap()
+			.collect::<Vec<_>>(),
+	);
+
+	assert_eq!(results.len(), 3);
+	assert_eq!(results[0].0, 0);
+	assert_eq!(*results[0].1.as_ref().unwrap(), api.hash_and_length(&xt0).0);
+	assert_eq!(results[1].0, 1);
+	assert!(results[1].1.is_err());
+	assert_eq!(results[2].0, 2);
+	assert_eq!(*results[2].1.as_ref().unwrap(), api.hash_and_length(&xt2).0);
+	assert_ready_iterator!(header01.hash(), pool, [xt0, xt2]);
+
+	// the transaction rejected by the view is removed from the mempool
+	assert_eq!(pool.mempool_len(), (2, 0));
+
+	// the transaction rejected by the mempool is yielded first
+	let xt3 = uxt(Alice, 202);
+	let results = block_on(
+		pool.submit_many_stream_at(header01.hash(), SOURCE, vec![xt3.clone(), xt0])
+			.unwrap()
+			.collect::<Vec<_>>(),
+	);
+	assert_eq!(results.len(), 2);
+	assert_eq!(results[0].0, 1);
+	assert!(matches!(results[0].1.as_ref().unwrap_err().0, TxPoolError::AlreadyImported(_)));
+	assert_eq!(results[1].0, 0);
+	assert_eq!(*results[1].1.as_ref().unwrap(), api.hash_and_length(&xt3).0);
+	assert_eq!(pool.mempool_len(), (3, 0));
+
+	assert!(pool.submit_many_stream_at(invalid_hash(), SOURCE, vec![]).is_none());
+}
+
+#[test]
+fn fatp_submit_local_with_priority_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 199);
+	api.set_priority(&xt0, 7);
+
+	let (hash, priority) = pool
+		.submit_local_with_priority_at(header01.hash(), xt0.clone())
+		.unwrap()
+		.unwrap();
+	assert_eq!((hash, priority), (api.hash_and_length(&xt0).0, 7));
+	assert!(pool.submit_local_with_priority_at(header01.hash(), xt1).unwrap().is_err());
+	assert_ready_iterator!(header01.hash(), pool, [xt0]);
+	assert_eq!(pool.mempool_len(), (1, 0));
+
+	assert!(pool.submit_local_with_priority_at(invalid_hash(), xt0).is_none());
+}
+
+#[test]
+fn fatp_submit_local_many_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 199);
+	let xt2 = uxt(Alice, 201);
+
+	let results = pool
+		.submit_local_many_at(header01.hash(), vec![xt0.clone(), xt1, xt2.clone()])
+		.unwrap();
+
+	assert_eq!(results.len(), 3);
+	assert_eq!(*results[0].as_ref().unwrap(), api.hash_and_length(&xt0).0);
+	assert!(results[1].is_err());
+	assert_eq!(*results[2].as_ref().unwrap(), api.hash_and_length(&xt2).0);
+	assert_ready_iterator!(header01.hash(), pool, [xt0, xt2]);
+	assert_eq!(pool.mempool_len(), (2, 0));
+
+	assert!(pool.submit_local_many_at(invalid_hash(), vec![]).is_none());
+}
+
+#[test]
+fn fatp_resubmit_removed_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	api.add_invalid(&xt1);
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap_err();
+	assert_eq!(pool.mempool_len(), (1, 0));
+
+	// the transaction kept in the mempool is not removed from there if rejected by the view
+	api.remove_invalid(&xt1);
+	let results =
+		block_on(pool.resubmit_removed_at(header01.hash(), SOURCE, vec![xt0.clone(), xt1.clone()]))
+			.unwrap();
+	assert!(results[0].is_err());
+	assert_eq!(*results[1].as_ref().unwrap(), api.hash_and_length(&xt1).0);
+	assert_ready_iterator!(header01.hash(), pool, [xt0, xt1]);
+	assert_eq!(pool.mempool_len(), (2, 0));
+
+	assert!(block_on(pool.resubmit_removed_at(invalid_hash(), SOURCE, vec![xt1])).is_none());
+}
+
+#[test]
+fn fatp_find_by_sender_nonce_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 202);
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap();
+
+	let extract = |xt: &Arc<_>| {
+		TransferData::try_from(&**xt)
+			.ok()
+			.map(|transfer| (transfer.from, transfer.nonce))
+	};
+	let alice = Alice.into();
+
+	assert_eq!(
+		pool.find_by_sender_nonce_at(header01.hash(), extract, &alice, 200),
+		Some(api.hash_and_length(&xt0).0)
+	);
+	assert_eq!(
+		pool.find_by_sender_nonce_at(header01.hash(), extract, &alice, 202),
+		Some(api.hash_and_length(&xt1).0)
+	);
+	assert_eq!(pool.find_by_sender_nonce_at(header01.hash(), extract, &alice, 201), None);
+	assert_eq!(pool.find_by_sender_nonce_at(header01.hash(), extract, &Bob.into(), 200), None);
+	assert_eq!(pool.find_by_sender_nonce_at(invalid_hash(), extract, &alice, 200), None);
+}
+
+#[test]
+fn fatp_estimate_block_fill_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+	api.set_nonce(header01.hash(), Bob.into(), 300);
+
+	let xts = vec![uxt(Alice, 200), uxt(Alice, 201), uxt(Alice, 202), uxt(Bob, 300)];
+	let results = block_on(pool.submit_at(header01.hash(), SOURCE, xts)).unwrap();
+	assert!(results.iter().all(Result::is_ok));
+	assert_pool_status!(header01.hash(), &pool, 4, 0);
+
+	// the transaction at nonce 201 is too heavy, so the one at nonce 202 is also skipped
+	let weight_of = |xt: &Arc<_>| {
+		let nonce = TransferData::try_from(&**xt).unwrap().nonce;
+		Weight::from_parts(if nonce == 201 { 100 } else { 10 }, 1)
+	};
+
+	assert_eq!(
+		pool.estimate_block_fill_at(header01.hash(), Weight::from_parts(50, 10), weight_of),
+		Some((2, Weight::from_parts(20, 2)))
+	);
+	assert_eq!(
+		pool.estimate_block_fill_at(header01.hash(), Weight::from_parts(200, 10), weight_of),
+		Some((4, Weight::from_parts(130, 4)))
+	);
+	assert_eq!(
+		pool.estimate_block_fill_at(header01.hash(), Weight::from_parts(200, 3), weight_of),
+		Some((3, Weight::from_parts(120, 3)))
+	);
+	assert_pool_status!(header01.hash(), &pool, 4, 0);
+	assert!(pool.estimate_block_fill_at(invalid_hash(), Weight::MAX, weight_of).is_none());
+}
+
+#[test]
+fn fatp_missing_tags_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Alice, 202);
+
+	assert!(pool.missing_tags_at(header01.hash()).unwrap().is_empty());
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt2.clone())).unwrap();
+	let missing_tags2 = pool.missing_tags_at(header01.hash()).unwrap();
+	assert_eq!(missing_tags2.len(), 1);
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap();
+	// the tag provided by future xt1 is still missing
+	let missing_tags1 = pool.missing_tags_at(header01.hash()).unwrap();
+	assert_eq!(missing_tags1.len(), 2);
+	assert!(missing_tags1.contains(&missing_tags2[0]));
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	assert!(pool.missing_tags_at(header01.hash()).unwrap().is_empty());
+	assert_pool_status!(header01.hash(), &pool, 3, 0);
+
+	assert!(pool.missing_tags_at(invalid_hash()).is_none());
+}
+
+#[test]
+fn fatp_provides_index_works() {
+	sp_tracing::try_init_simple();
+
+	let (pool, api, _) = pool();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt2 = uxt(Alice, 202);
+
+	assert!(pool.provides_index_at(header01.hash()).unwrap().is_empty());
+
+	let xt0_hash = block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt2.clone())).unwrap();
+	assert_pool_status!(header01.hash(), &pool, 1, 1);
+
+	// only the ready transaction is indexed, future one is waiting for the missing tag
+	let provides_index = pool.provides_index_at(header01.hash()).unwrap();
+	assert_eq!(provides_index.len(), 1);
+	assert!(provides_index.values().all(|hash| *hash == xt0_hash));
+	let missing_tags = pool.missing_tags_at(header01.hash()).unwrap();
+	assert!(missing_tags.iter().all(|tag| !provides_index.contains_key(tag)));
+
+	assert!(pool.provides_index_at(invalid_hash()).is_none());
+}
+
 #[test]
 fn fatp_no_view_future_and_ready_submit_works() {
 	sp_tracing::try_init_simple();
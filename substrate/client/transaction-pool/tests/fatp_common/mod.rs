@@ -23,7 +23,7 @@ use sc_transaction_pool_api::ChainEvent;
 use sp_runtime::transaction_validity::TransactionSource;
 use std::sync::Arc;
 use substrate_test_runtime_client::{
-	runtime::{Block, Hash, Header},
+	runtime::{Block, Extrinsic, ExtrinsicBuilder, Hash, Header},
 	Sr25519Keyring::*,
 };
 use substrate_test_runtime_transaction_pool::{uxt, TestApi};
@@ -35,6 +35,16 @@ pub fn invalid_hash() -> Hash {
 	Default::default()
 }
 
+/// Builds the extrinsic and computes its hash using the same `api` that is used by the pool.
+pub fn build_and_hash<A: ChainApi<Block = Block>>(
+	builder: ExtrinsicBuilder,
+	api: &A,
+) -> (Extrinsic, Hash) {
+	let xt = builder.build();
+	let hash = api.hash_and_length(&xt).0;
+	(xt, hash)
+}
+
 pub fn new_best_block_event(
 	pool: &ForkAwareTxPool<TestApi, Block>,
 	from: Option<Hash>,
@@ -22,7 +22,7 @@ pub mod fatp_common;
 
 use fatp_common::{invalid_hash, new_best_block_event, TestPoolBuilder, LOG_TARGET, SOURCE};
 use futures::{executor::block_on, FutureExt};
-use sc_transaction_pool::ChainApi;
+use sc_transaction_pool::{ChainApi, ReplaceOutcome};
 use sc_transaction_pool_api::{
 	error::Error as TxPoolError, LocalTransactionPool, MaintainedTransactionPool, TransactionPool,
 	TransactionStatus,
@@ -559,3 +559,49 @@ fn fatp_prios_submit_local_full_mempool_higher_prio_is_accepted() {
 	assert_ready_iterator!(header02.hash(), pool, [xt3, xt2]);
 	assert_ready_iterator!(header03.hash(), pool, [xt5, xt4]);
 }
+
+#[test]
+fn fatp_prios_replace_if_better_works() {
+	sp_tracing::try_init_simple();
+
+	let builder = TestPoolBuilder::new();
+	let (pool, api, _) = builder.build();
+
+	let header01 = api.push_block(1, vec![], true);
+	let event = new_best_block_event(&pool, None, header01.hash());
+	block_on(pool.maintain(event));
+
+	let xt0 = uxt(Alice, 200);
+	let xt1 = uxt(Alice, 201);
+	let xt2 = uxt(Alice, 200);
+	let xt3 = uxt(Alice, 200);
+
+	api.set_priority(&xt0, 2);
+	api.set_priority(&xt2, 2);
+	api.set_priority(&xt3, 3);
+
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt0.clone())).unwrap();
+	block_on(pool.submit_one(header01.hash(), SOURCE, xt1.clone())).unwrap();
+	let xt0_hash = api.hash_and_length(&xt0).0;
+
+	let outcome = block_on(pool.replace_if_better_at(header01.hash(), xt0_hash, SOURCE, xt2));
+	assert_eq!(outcome.unwrap().unwrap(), ReplaceOutcome::NotBetter);
+	assert_ready_iterator!(header01.hash(), pool, [xt0, xt1]);
+	assert_eq!(pool.mempool_len(), (2, 0));
+
+	let outcome =
+		block_on(pool.replace_if_better_at(header01.hash(), xt0_hash, SOURCE, xt3.clone()));
+	assert_eq!(outcome.unwrap().unwrap(), ReplaceOutcome::Replaced);
+	assert_pool_status!(header01.hash(), &pool, 2, 0);
+	assert_ready_iterator!(header01.hash(), pool, [xt3, xt1]);
+
+	// the replaced transaction is removed from the mempool
+	assert_eq!(pool.mempool_len(), (2, 0));
+
+	let outcome =
+		block_on(pool.replace_if_better_at(header01.hash(), xt0_hash, SOURCE, uxt(Alice, 200)));
+	assert_eq!(outcome.unwrap().unwrap(), ReplaceOutcome::NotFound);
+	assert_eq!(pool.mempool_len(), (2, 0));
+	let outcome = block_on(pool.replace_if_better_at(invalid_hash(), xt0_hash, SOURCE, xt0));
+	assert!(outcome.is_none());
+}
@@ -18,8 +18,9 @@
 //! Provides utils for building the `Extrinsic` instances used with `substrate-test-runtime`.
 
 use crate::{
-	substrate_test_pallet::pallet::Call as PalletCall, AccountId, Balance, BalancesCall,
-	CheckSubstrateCall, Extrinsic, Nonce, Pair, RuntimeCall, SignedPayload, TransferData,
+	genesismap::GenesisStorageBuilder, substrate_test_pallet::pallet::Call as PalletCall,
+	AccountId, Address, Balance, BalancesCall, CheckSubstrateCall, Executive, Extrinsic, Nonce,
+	Pair, Runtime, RuntimeCall, RuntimeOrigin, Signature, SignedPayload, TransferData,
 };
 use codec::Encode;
 use frame_metadata_hash_extension::CheckMetadataHash;
@@ -27,8 +28,15 @@ use frame_system::{CheckNonce, CheckWeight};
 use sp_core::crypto::Pair as TraitPair;
 use sp_keyring::Sr25519Keyring;
 use sp_runtime::{
-	generic::Preamble, traits::TransactionExtension, transaction_validity::TransactionPriority,
-	Perbill,
+	generic::Preamble,
+	traits::{Dispatchable, TransactionExtension},
+	transaction_validity::{TransactionPriority, TransactionSource, ValidTransaction},
+	BuildStorage, Perbill,
+};
+use std::{
+	cmp::Ordering,
+	panic::AssertUnwindSafe,
+	sync::atomic::{self, AtomicU64},
 };
 
 /// Transfer used in test substrate pallet. Extrinsic is created and signed using this data.
@@ -62,7 +70,26 @@ impl Default for TransferData {
 	}
 }
 
+impl TransferData {
+	/// Returns `true` if the transfer's nonce is equal to the `expected` one (e.g. the next nonce
+	/// of the sender's account).
+	pub fn validate_nonce_against(&self, expected: u64) -> bool {
+		self.compare_nonce(expected) == Ordering::Equal
+	}
+
+	/// Compares the transfer's nonce with the `expected` one.
+	///
+	/// `Ordering::Less` means the transfer is stale, `Ordering::Greater` means the transfer is
+	/// from the future (i.e. it has to wait for the transfers with lower nonces).
+	pub fn compare_nonce(&self, expected: u64) -> Ordering {
+		self.nonce.cmp(&expected)
+	}
+}
+
 /// If feasible converts given `Extrinsic` to `TransferData`
+///
+/// The amount transferred by `transfer_all` is only known at dispatch, so it is converted with
+/// `amount` set to `Balance::MAX`.
 impl TryFrom<&Extrinsic> for TransferData {
 	type Error = ();
 	fn try_from(uxt: &Extrinsic) -> Result<Self, Self::Error> {
@@ -71,6 +98,10 @@ impl TryFrom<&Extrinsic> for TransferData {
 				function: RuntimeCall::Balances(BalancesCall::transfer_allow_death { dest, value }),
 				preamble: Preamble::Signed(from, _, ((CheckNonce(nonce), ..), ..)),
 			} => Ok(TransferData { from: *from, to: *dest, amount: *value, nonce: *nonce }),
+			Extrinsic {
+				function: RuntimeCall::Balances(BalancesCall::transfer_all { dest, .. }),
+				preamble: Preamble::Signed(from, _, ((CheckNonce(nonce), ..), ..)),
+			} => Ok(TransferData { from: *from, to: *dest, amount: Balance::MAX, nonce: *nonce }),
 			Extrinsic {
 				function: RuntimeCall::SubstrateTest(PalletCall::bench_call { transfer }),
 				preamble: Preamble::Bare(_),
@@ -81,11 +112,13 @@ impl TryFrom<&Extrinsic> for TransferData {
 }
 
 /// Generates `Extrinsic`
+#[derive(Clone)]
 pub struct ExtrinsicBuilder {
 	function: RuntimeCall,
 	signer: Option<Pair>,
 	nonce: Option<Nonce>,
 	metadata_hash: Option<[u8; 32]>,
+	raw_signature: Option<(Address, Signature)>,
 }
 
 impl ExtrinsicBuilder {
@@ -96,12 +129,19 @@ impl ExtrinsicBuilder {
 			signer: Some(Sr25519Keyring::Alice.pair()),
 			nonce: None,
 			metadata_hash: None,
+			raw_signature: None,
 		}
 	}
 
 	/// Create builder for given `RuntimeCall`. `Extrinsic` will be unsigned.
 	pub fn new_unsigned(function: impl Into<RuntimeCall>) -> Self {
-		Self { function: function.into(), signer: None, nonce: None, metadata_hash: None }
+		Self {
+			function: function.into(),
+			signer: None,
+			nonce: None,
+			metadata_hash: None,
+			raw_signature: None,
+		}
 	}
 
 	/// Create builder for `pallet_call::bench_transfer` from given `TransferData`.
@@ -123,6 +163,49 @@ impl ExtrinsicBuilder {
 		}
 	}
 
+	/// Create builder for `BalancesCall::transfer_all` signed by `from` at given `nonce`.
+	///
+	/// The whole transferable balance of `from` is transferred to `to`. If `keep_alive` is `false`
+	/// the sender account may be reaped.
+	pub fn new_transfer_all(from: Pair, to: AccountId, keep_alive: bool, nonce: u64) -> Self {
+		Self {
+			nonce: Some(nonce),
+			signer: Some(from),
+			..Self::new(BalancesCall::transfer_all { dest: to, keep_alive })
+		}
+	}
+
+	/// Create a pair of transfer extrinsics conflicting with each other.
+	///
+	/// Both extrinsics are signed by `transfer.from` at `transfer.nonce`, so they provide the same
+	/// tag. The first one transfers `transfer.amount`, the second one transfers a different amount
+	/// (`transfer.amount + 1`, or `transfer.amount - 1` on overflow), so the second one can be used
+	/// as a replacement of the first one.
+	pub fn conflicting_pair(transfer: Transfer) -> (Extrinsic, Extrinsic) {
+		let amount = transfer.amount.checked_add(1).unwrap_or_else(|| transfer.amount - 1);
+		let first = Self::new_transfer(transfer.clone()).build();
+		let second = Self::new_transfer(Transfer { amount, ..transfer }).build();
+		(first, second)
+	}
+
+	/// Create a transfer extrinsic with a stale nonce.
+	///
+	/// The extrinsic is built from `transfer` with its nonce overridden to `0`. It is only stale if
+	/// the on-chain nonce of `transfer.from` has already been advanced past `0` (e.g. by including
+	/// an earlier transaction of the account), in which case `validate_transaction` rejects it with
+	/// `InvalidTransaction::Stale`. At genesis the returned extrinsic is valid.
+	pub fn build_stale_nonce(transfer: Transfer) -> Extrinsic {
+		Self::new_transfer(Transfer { nonce: 0, ..transfer }).build()
+	}
+
+	/// Create builder for given `Transfer` with given `metadata_hash` put into the signed data of
+	/// the extrinsic.
+	///
+	/// Equivalent to `ExtrinsicBuilder::new_transfer(transfer).metadata_hash(metadata_hash)`.
+	pub fn new_transfer_with_metadata_hash(transfer: Transfer, metadata_hash: [u8; 32]) -> Self {
+		Self::new_transfer(transfer).metadata_hash(metadata_hash)
+	}
+
 	/// Create builder for `PalletCall::include_data` call using given parameters
 	pub fn new_include_data(data: Vec<u8>) -> Self {
 		Self::new(PalletCall::include_data { data })
@@ -149,6 +232,13 @@ impl ExtrinsicBuilder {
 		Self::new(PalletCall::indexed_call { data })
 	}
 
+	/// Create builder for `PalletCall::indexed_call` call with the payload of exactly `len` bytes.
+	///
+	/// The payload is deterministic: the byte at position `i` is equal to `i % 256`.
+	pub fn new_indexed_call_sized(len: usize) -> Self {
+		Self::new_indexed_call((0..len).map(|i| i as u8).collect())
+	}
+
 	/// Create builder for `PalletCall::new_deposit_log_digest_item` call using given `log`
 	pub fn new_deposit_log_digest_item(log: sp_runtime::generic::DigestItem) -> Self {
 		Self::new_unsigned(PalletCall::deposit_log_digest_item { log })
@@ -159,16 +249,70 @@ impl ExtrinsicBuilder {
 		Self::new(PalletCall::fill_block { ratio })
 	}
 
+	/// Create builder for `PalletCall::overweight_call` call
+	///
+	/// The declared weight of the call exceeds the maximal block weight (unlike
+	/// [`Self::new_fill_block`], which is capped at the full block), so the resulting transaction
+	/// can never be included. Its validation is expected to fail with
+	/// `InvalidTransaction::ExhaustsResources`.
+	pub fn new_overweight_call() -> Self {
+		Self::new(PalletCall::overweight_call {})
+	}
+
 	/// Create builder for `PalletCall::call_do_not_propagate` call using given parameters
+	///
+	/// The validity of the resulting transaction has `propagate` set to `false`, use
+	/// [`Self::build_and_validate`] to check it. The extrinsic is signed by Alice, refer to
+	/// [`Self::new_call_do_not_propagate_signed`] for other signers.
 	pub fn new_call_do_not_propagate() -> Self {
 		Self::new(PalletCall::call_do_not_propagate {})
 	}
 
+	/// Create builder for `PalletCall::call_do_not_propagate` call signed by given `signer`.
+	///
+	/// Equivalent to `ExtrinsicBuilder::new_call_do_not_propagate().signer(signer)`. The
+	/// `propagate` flag of the validity does not depend on the signer.
+	pub fn new_call_do_not_propagate_signed(signer: Pair) -> Self {
+		Self::new_call_do_not_propagate().signer(signer)
+	}
+
 	/// Create builder for `PalletCall::call_with_priority` call using given parameters
+	///
+	/// The priority of the transaction seen by the pool is the sum of priorities returned by
+	/// all the transaction extensions (see `ValidTransaction::combine_with`). None of the other
+	/// extensions of the test runtime contributes to the priority and there is no tip (no
+	/// payment pallet) in the test runtime, so the final priority is equal to the given
+	/// `priority`.
 	pub fn new_call_with_priority(priority: TransactionPriority) -> Self {
 		Self::new(PalletCall::call_with_priority { priority })
 	}
 
+	/// Create builder for `PalletCall::call_with_tags` call using given parameters. Will create
+	/// unsigned Extrinsic.
+	///
+	/// The validity of the resulting transaction carries exactly the given `requires` and
+	/// `provides` tags, giving the deterministic control over the dependencies between the
+	/// transactions. Note that the pool rejects the transaction that provides no tags. If the
+	/// extrinsic is signed (see [`Self::signer`]) the tags derived from the signer's nonce are
+	/// added.
+	pub fn new_tagged_call(requires: Vec<Vec<u8>>, provides: Vec<Vec<u8>>) -> Self {
+		Self::new_unsigned(PalletCall::call_with_tags { requires, provides })
+	}
+
+	/// Create a pair of distinct extrinsics providing the same tag with the same priority.
+	///
+	/// Both extrinsics are unsigned `PalletCall::call_with_tags` calls with no requirements, both
+	/// providing the `b"equal_priority_conflict"` tag. To make them distinct, the second one
+	/// additionally provides the `b"equal_priority_conflict_2"` tag. Neither of them is a valid
+	/// replacement of the other one.
+	pub fn equal_priority_conflict() -> (Extrinsic, Extrinsic) {
+		let tag = b"equal_priority_conflict".to_vec();
+		let first = Self::new_tagged_call(vec![], vec![tag.clone()]).build();
+		let second =
+			Self::new_tagged_call(vec![], vec![tag, b"equal_priority_conflict_2".to_vec()]).build();
+		(first, second)
+	}
+
 	/// Create builder for `PalletCall::read` call using given parameters
 	pub fn new_read(count: u32) -> Self {
 		Self::new_unsigned(PalletCall::read { count })
@@ -180,8 +324,11 @@ impl ExtrinsicBuilder {
 	}
 
 	/// Unsigned `Extrinsic` will be created
+	///
+	/// The nonce is cleared as well, as it is meaningless for the bare extrinsic.
 	pub fn unsigned(mut self) -> Self {
 		self.signer = None;
+		self.nonce = None;
 		self
 	}
 
@@ -191,6 +338,14 @@ impl ExtrinsicBuilder {
 		self
 	}
 
+	/// The nonce fetched from the shared `counter` will be set in `Extrinsic`
+	///
+	/// The counter is incremented, so the builders sharing the same counter get the consecutive
+	/// nonces (in the order the method is called).
+	pub fn nonce_auto(self, counter: &AtomicU64) -> Self {
+		self.nonce(counter.fetch_add(1, atomic::Ordering::Relaxed))
+	}
+
 	/// Extrinsic will be signed by `signer`
 	pub fn signer(mut self, signer: Pair) -> Self {
 		self.signer = Some(signer);
@@ -203,27 +358,221 @@ impl ExtrinsicBuilder {
 		self
 	}
 
+	/// Given `signature` of `signer` will be put into `Extrinsic` verbatim, instead of signing the
+	/// payload with the `signer` key.
+	///
+	/// Allows to reproduce exact extrinsics (e.g. captured from the network). Takes precedence
+	/// over the `signer`. Note that the signature is not checked when building the extrinsic.
+	pub fn raw_signature(mut self, signer: Address, signature: Signature) -> Self {
+		self.raw_signature = Some((signer, signature));
+		self
+	}
+
+	/// Returns the `TransferData` embedded in the configured call, if it is the
+	/// `PalletCall::bench_call` (see [`Self::new_bench_call`]).
+	///
+	/// Allows to inspect the transfer before the `Extrinsic` is built.
+	pub fn bench_transfer(&self) -> Option<&TransferData> {
+		match &self.function {
+			RuntimeCall::SubstrateTest(PalletCall::bench_call { transfer }) => Some(transfer),
+			_ => None,
+		}
+	}
+
+	/// Build `Extrinsic` using embedded parameters and validate it at genesis.
+	///
+	/// Returns the extrinsic together with its validity, so tests can assert the validity fields
+	/// (e.g. `propagate` for [`Self::new_call_do_not_propagate`]) directly. Panics if the extrinsic
+	/// is not valid, refer to [`assert_valid`] for more details.
+	pub fn build_and_validate(self) -> (Extrinsic, ValidTransaction) {
+		let xt = self.build();
+		let validity = assert_valid(&xt);
+		(xt, validity)
+	}
+
 	/// Build `Extrinsic` using embedded parameters
 	pub fn build(self) -> Extrinsic {
-		if let Some(signer) = self.signer {
-			let tx_ext = (
-				(CheckNonce::from(self.nonce.unwrap_or(0)), CheckWeight::new()),
-				CheckSubstrateCall {},
-				self.metadata_hash
-					.map(CheckMetadataHash::new_with_custom_hash)
-					.unwrap_or_else(|| CheckMetadataHash::new(false)),
-				frame_system::WeightReclaim::new(),
-			);
-			let raw_payload = SignedPayload::from_raw(
-				self.function.clone(),
-				tx_ext.clone(),
-				tx_ext.implicit().unwrap(),
-			);
-			let signature = raw_payload.using_encoded(|e| signer.sign(e));
-
-			Extrinsic::new_signed(self.function, signer.public(), signature, tx_ext)
-		} else {
-			Extrinsic::new_bare(self.function)
+		self.build_with_payload().0
+	}
+
+	/// Build `Extrinsic` using embedded parameters, returning also the signed payload.
+	///
+	/// The payload is the exact sequence of bytes the signature is created for (note that the
+	/// payload longer than 256 bytes is hashed), so the signature can be verified or reproduced
+	/// externally. If the signature is given by [`Self::raw_signature`], the returned payload is
+	/// the one the signature is expected to be created for. The payload is empty for the unsigned
+	/// `Extrinsic`.
+	pub fn build_with_payload(self) -> (Extrinsic, Vec<u8>) {
+		if self.signer.is_none() && self.raw_signature.is_none() {
+			return (Extrinsic::new_bare(self.function), Vec::new())
+		}
+
+		let tx_ext = (
+			(CheckNonce::from(self.nonce.unwrap_or(0)), CheckWeight::new()),
+			CheckSubstrateCall {},
+			self.metadata_hash
+				.map(CheckMetadataHash::new_with_custom_hash)
+				.unwrap_or_else(|| CheckMetadataHash::new(false)),
+			frame_system::WeightReclaim::new(),
+		);
+		let raw_payload = SignedPayload::from_raw(
+			self.function.clone(),
+			tx_ext.clone(),
+			tx_ext.implicit().unwrap(),
+		);
+		let payload = raw_payload.using_encoded(|e| e.to_vec());
+
+		let (address, signature) = match (self.raw_signature, self.signer) {
+			(Some(raw_signature), _) => raw_signature,
+			(None, signer) => {
+				let signer = signer.expect("Signer is set, checked above. qed");
+				(signer.public(), signer.sign(&payload))
+			},
+		};
+
+		(Extrinsic::new_signed(self.function, address, signature, tx_ext), payload)
+	}
+}
+
+/// Kind of the call generated by the [`WorkloadBuilder`] for the signed extrinsics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WorkloadCall {
+	/// `BalancesCall::transfer_allow_death` of 1 unit to `Bob`.
+	Transfer,
+	/// `PalletCall::include_data` with the payload unique for the signer and nonce.
+	IncludeData,
+	/// `PalletCall::call_with_priority` with given priority.
+	CallWithPriority(TransactionPriority),
+}
+
+/// Generates a mixed workload of `Extrinsic`s, intended as a standard setup of the pool tests and
+/// benchmarks.
+///
+/// The workload consists of the extrinsics signed by the configured signers and of the unsigned
+/// extrinsics (`PalletCall::storage_change` with the unique key). The nonces of every signer are
+/// consecutive, starting at `0`. The call of the `n`-th extrinsic of the signer is picked
+/// deterministically according to the configured call weights: in every cycle of `total_weight`
+/// extrinsics each call kind is used `weight` times. If no call is configured, transfers are
+/// generated.
+///
+/// The extrinsics are interleaved: the `n`-th extrinsic of every signer (and the `n`-th unsigned
+/// extrinsic) is followed by the `n+1`-th ones.
+#[derive(Clone, Default)]
+pub struct WorkloadBuilder {
+	signers: Vec<(Pair, usize)>,
+	unsigned: usize,
+	calls: Vec<(WorkloadCall, u32)>,
+}
+
+impl WorkloadBuilder {
+	/// Create builder of the empty workload.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds `count` extrinsics signed by `signer` to the workload.
+	pub fn signed(mut self, signer: Pair, count: usize) -> Self {
+		self.signers.push((signer, count));
+		self
+	}
+
+	/// Adds `count` unsigned extrinsics to the workload.
+	pub fn unsigned(mut self, count: usize) -> Self {
+		self.unsigned += count;
+		self
+	}
+
+	/// Uses given `call` for the signed extrinsics with given relative `weight`.
+	pub fn call(mut self, call: WorkloadCall, weight: u32) -> Self {
+		self.calls.push((call, weight));
+		self
+	}
+
+	/// Build the `Extrinsic`s of the workload.
+	pub fn build(self) -> Vec<Extrinsic> {
+		let mut calls = self
+			.calls
+			.iter()
+			.flat_map(|(call, weight)| std::iter::repeat(*call).take(*weight as usize))
+			.collect::<Vec<_>>();
+		if calls.is_empty() {
+			calls.push(WorkloadCall::Transfer);
 		}
+
+		let rounds = self.signers.iter().map(|(_, count)| *count).chain([self.unsigned]).max();
+		let mut workload = Vec::new();
+		for nonce in 0..rounds.unwrap_or_default() as u64 {
+			for (signer, _) in self.signers.iter().filter(|(_, count)| nonce < *count as u64) {
+				let builder = match calls[nonce as usize % calls.len()] {
+					WorkloadCall::Transfer => ExtrinsicBuilder::new_transfer(Transfer {
+						from: signer.clone(),
+						to: Sr25519Keyring::Bob.into(),
+						amount: 1,
+						nonce,
+					}),
+					WorkloadCall::IncludeData =>
+						ExtrinsicBuilder::new_include_data((signer.public(), nonce).encode()),
+					WorkloadCall::CallWithPriority(priority) =>
+						ExtrinsicBuilder::new_call_with_priority(priority),
+				};
+				workload.push(builder.signer(signer.clone()).nonce(nonce).build());
+			}
+			if nonce < self.unsigned as u64 {
+				let key = (b"workload", nonce).encode();
+				workload.push(ExtrinsicBuilder::new_storage_change(key, Some(vec![1])).build());
+			}
+		}
+		workload
 	}
 }
+
+/// Asserts that given `Extrinsic` is valid at genesis and returns its validity.
+///
+/// The runtime's `validate_transaction` is executed natively on top of the default genesis state
+/// (see [`GenesisStorageBuilder::default`]), so the wasm binary is not required. Intended as a
+/// one-line guard in tests, catching the builder misconfiguration (e.g. mismatched signed payload)
+/// early.
+///
+/// Panics with a descriptive message if the extrinsic is not valid.
+pub fn assert_valid(ext: &Extrinsic) -> ValidTransaction {
+	let validity = genesis_externalities().execute_with(|| {
+		let genesis_hash = frame_system::Pallet::<Runtime>::block_hash(0);
+		Executive::validate_transaction(TransactionSource::External, ext.clone(), genesis_hash)
+	});
+	validity.unwrap_or_else(|error| {
+		panic!("Extrinsic is not valid at genesis: {error:?}, extrinsic: {ext:?}")
+	})
+}
+
+/// Builds the [`ExtrinsicBuilder::new_read_and_panic`] extrinsic and asserts that only its
+/// execution panics.
+///
+/// The validation of the extrinsic does not dispatch the call, so it is expected to succeed at
+/// genesis (refer to [`assert_valid`]), while dispatching the call on top of the genesis state is
+/// expected to panic. This guards the boundary between the validation and the execution, which
+/// the panic-isolation tests rely on. Note that the call does not panic if `count` exceeds the
+/// number of keys in the genesis state.
+///
+/// Panics with a descriptive message if any of the expectations is not met.
+pub fn assert_read_and_panic(count: u32) -> Extrinsic {
+	let ext = ExtrinsicBuilder::new_read_and_panic(count).build();
+	assert_valid(&ext);
+	let call = ext.function.clone();
+	let dispatched = genesis_externalities().execute_with(|| {
+		std::panic::catch_unwind(AssertUnwindSafe(|| call.dispatch(RuntimeOrigin::none())))
+	});
+	if let Ok(result) = dispatched {
+		panic!("Execution of extrinsic did not panic: {result:?}, extrinsic: {ext:?}")
+	}
+	ext
+}
+
+/// Creates the externalities with the default genesis state.
+fn genesis_externalities() -> sp_io::TestExternalities {
+	// The runtime code is not needed for the native execution.
+	GenesisStorageBuilder::default()
+		.genesis_config()
+		.build_storage()
+		.expect("Build storage from substrate-test-runtime RuntimeGenesisConfig")
+		.into()
+}
@@ -140,7 +140,9 @@ pub fn native_version() -> NativeVersion {
 }
 
 /// Transfer data extracted from Extrinsic containing `Balances::transfer_allow_death`.
-#[derive(Clone, PartialEq, Eq, Encode, Decode, DecodeWithMemTracking, RuntimeDebug, TypeInfo)]
+#[derive(
+	Clone, PartialEq, Eq, Hash, Encode, Decode, DecodeWithMemTracking, RuntimeDebug, TypeInfo,
+)]
 pub struct TransferData {
 	pub from: AccountId,
 	pub to: AccountId,
@@ -1152,7 +1154,8 @@ mod tests {
 	use sp_consensus::BlockOrigin;
 	use sp_core::{storage::well_known_keys::HEAP_PAGES, traits::CallContext};
 	use sp_runtime::{
-		traits::{DispatchTransaction, Hash as _},
+		generic::Preamble,
+		traits::{DispatchTransaction, Hash as _, TransactionExtension},
 		transaction_validity::{InvalidTransaction, TransactionSource::External, ValidTransaction},
 	};
 	use substrate_test_runtime_client::{
@@ -1346,6 +1349,437 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn new_transfer_with_metadata_hash_works() {
+		let transfer = || Transfer {
+			from: Sr25519Keyring::Alice.pair(),
+			to: Sr25519Keyring::Bob.into(),
+			amount: 1,
+			nonce: 0,
+		};
+		let implicit = |xt: Extrinsic| match xt.preamble {
+			Preamble::Signed(_, _, tx_ext) => tx_ext.implicit().unwrap(),
+			_ => panic!("extrinsic shall be signed"),
+		};
+
+		let xt = ExtrinsicBuilder::new_transfer_with_metadata_hash(transfer(), [1u8; 32]).build();
+		let chained = ExtrinsicBuilder::new_transfer(transfer()).metadata_hash([1u8; 32]).build();
+		let no_hash = ExtrinsicBuilder::new_transfer(transfer()).build();
+
+		assert_eq!(TransferData::try_from(&xt).unwrap(), TransferData::try_from(&no_hash).unwrap());
+		assert_eq!(implicit(xt.clone()), implicit(chained));
+		assert_ne!(implicit(xt), implicit(no_hash));
+	}
+
+	#[test]
+	fn raw_signature_works() {
+		let builder = || ExtrinsicBuilder::new_include_data(vec![1, 2, 3]).nonce(5);
+		let xt = builder().build();
+		let (address, signature) = match xt.preamble {
+			Preamble::Signed(address, signature, _) => (address, signature),
+			_ => panic!("extrinsic shall be signed"),
+		};
+
+		let replayed = builder()
+			.signer(Sr25519Keyring::Bob.pair())
+			.raw_signature(address, signature)
+			.build();
+		assert_eq!(replayed, xt);
+
+		let bob = Sr25519Keyring::Bob.public();
+		let forged = builder().raw_signature(bob, signature).build();
+		assert!(matches!(
+			forged.preamble,
+			Preamble::Signed(address, s, _) if address == bob && s == signature
+		));
+	}
+
+	#[test]
+	fn build_with_payload_works() {
+		let (xt, payload) = ExtrinsicBuilder::new_include_data(vec![1, 2, 3])
+			.signer(Sr25519Keyring::Bob.pair())
+			.nonce(5)
+			.build_with_payload();
+		let Preamble::Signed(address, signature, _) = xt.preamble else {
+			panic!("extrinsic shall be signed")
+		};
+		assert!(<sp_core::sr25519::Pair as sp_core::Pair>::verify(&signature, &payload, &address));
+
+		// the payload of the long call is hashed before signing.
+		let (xt, payload) = ExtrinsicBuilder::new_include_data(vec![1; 512]).build_with_payload();
+		assert_eq!(payload.len(), 32);
+		let Preamble::Signed(address, signature, _) = xt.preamble else {
+			panic!("extrinsic shall be signed")
+		};
+		assert!(<sp_core::sr25519::Pair as sp_core::Pair>::verify(&signature, &payload, &address));
+
+		let (_, payload) = ExtrinsicBuilder::new_include_data(vec![1, 2, 3])
+			.unsigned()
+			.build_with_payload();
+		assert!(payload.is_empty());
+	}
+
+	#[test]
+	fn cloned_extrinsic_builder_works() {
+		let base =
+			ExtrinsicBuilder::new_include_data(vec![1, 2, 3]).signer(Sr25519Keyring::Bob.pair());
+
+		let signed_parts = |xt: Extrinsic| match xt.preamble {
+			Preamble::Signed(address, _, ext) => (xt.function, address, ext),
+			_ => panic!("extrinsic shall be signed"),
+		};
+
+		// sr25519 signatures are randomized, so only the signed parts are compared.
+		let xt = base.clone().nonce(7).build();
+		assert_eq!(signed_parts(base.clone().nonce(7).build()), signed_parts(xt.clone()));
+		assert_ne!(signed_parts(base.clone().nonce(8).build()), signed_parts(xt));
+
+		let unsigned = base.unsigned();
+		assert_eq!(unsigned.clone().build(), unsigned.build());
+	}
+
+	#[test]
+	fn unsigned_transfer_is_clean_bare_extrinsic() {
+		let transfer = Transfer {
+			from: Sr25519Keyring::Alice.into(),
+			to: Sr25519Keyring::Bob.into(),
+			amount: 69,
+			nonce: 5,
+		};
+		let call = BalancesCall::transfer_allow_death { dest: transfer.to, value: transfer.amount };
+
+		let unsigned = ExtrinsicBuilder::new_transfer(transfer).unsigned();
+		assert_eq!(unsigned.clone().build(), ExtrinsicBuilder::new_unsigned(call).build());
+
+		// the nonce of the transfer does not leak into the re-signed extrinsic.
+		let xt = unsigned.signer(Sr25519Keyring::Alice.pair()).build();
+		assert_eq!(TransferData::try_from(&xt).unwrap().nonce, 0);
+	}
+
+	#[test]
+	fn assert_valid_works() {
+		let transfer = Transfer {
+			from: Sr25519Keyring::Alice.pair(),
+			to: Sr25519Keyring::Bob.into(),
+			amount: 100,
+			nonce: 0,
+		};
+		extrinsic::assert_valid(&ExtrinsicBuilder::new_transfer(transfer).build());
+		let validity =
+			extrinsic::assert_valid(&ExtrinsicBuilder::new_call_with_priority(16).build());
+		assert_eq!(validity.priority, 16);
+	}
+
+	#[test]
+	fn assert_read_and_panic_works() {
+		let xt = extrinsic::assert_read_and_panic(0);
+		assert!(matches!(
+			xt.function,
+			RuntimeCall::SubstrateTest(substrate_test_pallet::Call::read_and_panic { count: 0 })
+		));
+		extrinsic::assert_read_and_panic(1);
+	}
+
+	#[test]
+	fn new_indexed_call_sized_works() {
+		let data_of = |xt: Extrinsic| match xt.function {
+			RuntimeCall::SubstrateTest(substrate_test_pallet::Call::indexed_call { data }) => data,
+			_ => panic!("indexed call expected"),
+		};
+
+		assert!(data_of(ExtrinsicBuilder::new_indexed_call_sized(0).build()).is_empty());
+		let data = data_of(ExtrinsicBuilder::new_indexed_call_sized(300).build());
+		assert_eq!(data.len(), 300);
+		assert_eq!(data, data_of(ExtrinsicBuilder::new_indexed_call_sized(300).build()));
+		assert_eq!((data[0], data[255], data[256]), (0, 255, 0));
+	}
+
+	#[test]
+	fn new_transfer_all_works() {
+		let xt = ExtrinsicBuilder::new_transfer_all(
+			Sr25519Keyring::Alice.into(),
+			Sr25519Keyring::Bob.into(),
+			true,
+			0,
+		)
+		.build();
+		assert!(matches!(
+			xt.function,
+			RuntimeCall::Balances(pallet_balances::Call::transfer_all { keep_alive: true, .. })
+		));
+
+		let transfer = TransferData::try_from(&xt).unwrap();
+		assert_eq!(transfer.from, Sr25519Keyring::Alice.into());
+		assert_eq!(transfer.to, Sr25519Keyring::Bob.into());
+		assert_eq!(transfer.amount, Balance::MAX);
+		assert_eq!(transfer.nonce, 0);
+
+		extrinsic::assert_valid(&xt);
+	}
+
+	#[test]
+	fn conflicting_pair_works() {
+		let transfer = Transfer {
+			from: Sr25519Keyring::Alice.into(),
+			to: Sr25519Keyring::Bob.into(),
+			amount: 69,
+			nonce: 5,
+		};
+		let (first, second) = ExtrinsicBuilder::conflicting_pair(transfer);
+		assert_ne!(first, second);
+
+		let first = TransferData::try_from(&first).unwrap();
+		let second = TransferData::try_from(&second).unwrap();
+		assert_eq!((first.from, first.to, first.nonce), (second.from, second.to, second.nonce));
+		assert_eq!((first.amount, second.amount), (69, 70));
+		assert_eq!(first.nonce, 5);
+
+		let amounts = |amount| {
+			let (first, second) = ExtrinsicBuilder::conflicting_pair(Transfer {
+				from: Sr25519Keyring::Alice.into(),
+				to: Sr25519Keyring::Bob.into(),
+				amount,
+				nonce: 5,
+			});
+			let first = TransferData::try_from(&first).unwrap();
+			let second = TransferData::try_from(&second).unwrap();
+			(first.amount, second.amount)
+		};
+		assert_eq!(amounts(0), (0, 1));
+		assert_eq!(amounts(u64::MAX), (u64::MAX, u64::MAX - 1));
+	}
+
+	#[test]
+	fn nonce_auto_works() {
+		let counter = std::sync::atomic::AtomicU64::new(3);
+		let nonces = (0..3)
+			.map(|_| {
+				let xt = ExtrinsicBuilder::new_transfer(Transfer {
+					from: Sr25519Keyring::Alice.into(),
+					to: Sr25519Keyring::Bob.into(),
+					amount: 69,
+					nonce: 0,
+				})
+				.nonce_auto(&counter)
+				.build();
+				TransferData::try_from(&xt).unwrap().nonce
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(nonces, vec![3, 4, 5]);
+		assert_eq!(counter.into_inner(), 6);
+	}
+
+	#[test]
+	fn equal_priority_conflict_works() {
+		let (first, second) = ExtrinsicBuilder::equal_priority_conflict();
+		assert_ne!(first, second);
+
+		let first = extrinsic::assert_valid(&first);
+		let second = extrinsic::assert_valid(&second);
+		assert_eq!(first.priority, second.priority);
+		assert!(first.provides.iter().all(|tag| second.provides.contains(tag)));
+		assert!(first.requires.is_empty() && second.requires.is_empty());
+	}
+
+	#[test]
+	fn build_stale_nonce_works() {
+		let transfer = Transfer {
+			from: Sr25519Keyring::Alice.into(),
+			to: Sr25519Keyring::Bob.into(),
+			amount: 69,
+			nonce: 5,
+		};
+		let xt = ExtrinsicBuilder::build_stale_nonce(transfer);
+		assert_eq!(TransferData::try_from(&xt).unwrap().nonce, 0);
+
+		// Native execution only, the runtime code is not needed.
+		let genesis_config = genesismap::GenesisStorageBuilder::default().genesis_config();
+		let mut externalities: sp_io::TestExternalities =
+			sp_runtime::BuildStorage::build_storage(&genesis_config).unwrap().into();
+		externalities.execute_with(|| {
+			let genesis_hash = frame_system::Pallet::<Runtime>::block_hash(0);
+			let validate = || {
+				Executive::validate_transaction(
+					TransactionSource::External,
+					xt.clone(),
+					genesis_hash,
+				)
+			};
+			assert!(validate().is_ok());
+
+			frame_system::Pallet::<Runtime>::inc_account_nonce(AccountId::from(
+				Sr25519Keyring::Alice,
+			));
+			assert_eq!(validate(), Err(InvalidTransaction::Stale.into()));
+		});
+	}
+
+	#[test]
+	fn transfer_data_can_be_compared_and_deduplicated() {
+		let transfer = Transfer {
+			from: Sr25519Keyring::Alice.into(),
+			to: Sr25519Keyring::Bob.into(),
+			amount: 69,
+			nonce: 5,
+		};
+		let expected = TransferData {
+			from: Sr25519Keyring::Alice.public(),
+			to: Sr25519Keyring::Bob.public(),
+			amount: 69,
+			nonce: 5,
+		};
+		let (first, second) = ExtrinsicBuilder::conflicting_pair(transfer.clone());
+		let xts = [
+			ExtrinsicBuilder::new_transfer(transfer.clone()).build(),
+			ExtrinsicBuilder::new_transfer(transfer).build(),
+			first,
+			second,
+		];
+
+		let decoded = xts.iter().map(|xt| TransferData::try_from(xt).unwrap()).collect::<Vec<_>>();
+		assert_eq!(decoded[0], expected);
+		assert_eq!(decoded.iter().collect::<std::collections::HashSet<_>>().len(), 2);
+	}
+
+	#[test]
+	fn new_overweight_call_exhausts_resources() {
+		let xt = ExtrinsicBuilder::new_overweight_call().build();
+		let genesis_config = genesismap::GenesisStorageBuilder::default().genesis_config();
+		let mut externalities: sp_io::TestExternalities =
+			sp_runtime::BuildStorage::build_storage(&genesis_config).unwrap().into();
+		let validity = externalities.execute_with(|| {
+			let genesis_hash = frame_system::Pallet::<Runtime>::block_hash(0);
+			Executive::validate_transaction(TransactionSource::External, xt, genesis_hash)
+		});
+		assert_eq!(validity, Err(InvalidTransaction::ExhaustsResources.into()));
+	}
+
+	#[test]
+	fn transfer_data_nonce_can_be_checked() {
+		let transfer = TransferData { nonce: 5, ..Default::default() };
+
+		assert!(transfer.validate_nonce_against(5));
+		assert!(!transfer.validate_nonce_against(4));
+		assert_eq!(transfer.compare_nonce(6), std::cmp::Ordering::Less);
+		assert_eq!(transfer.compare_nonce(5), std::cmp::Ordering::Equal);
+		assert_eq!(transfer.compare_nonce(4), std::cmp::Ordering::Greater);
+	}
+
+	#[test]
+	fn bench_transfer_works() {
+		let transfer = TransferData {
+			from: Sr25519Keyring::Alice.public(),
+			to: Sr25519Keyring::Bob.public(),
+			amount: 69,
+			nonce: 5,
+		};
+		let builder = ExtrinsicBuilder::new_bench_call(transfer.clone());
+		assert_eq!(builder.bench_transfer(), Some(&transfer));
+		assert_eq!(TransferData::try_from(&builder.build()), Ok(transfer));
+
+		assert_eq!(ExtrinsicBuilder::new_call_do_not_propagate().bench_transfer(), None);
+	}
+
+	#[test]
+	fn workload_builder_works() {
+		use extrinsic::{WorkloadBuilder, WorkloadCall};
+
+		let workload = WorkloadBuilder::new()
+			.signed(Sr25519Keyring::Alice.pair(), 3)
+			.signed(Sr25519Keyring::Bob.pair(), 1)
+			.unsigned(2)
+			.call(WorkloadCall::Transfer, 2)
+			.call(WorkloadCall::CallWithPriority(7), 1)
+			.build();
+
+		let summary = workload
+			.iter()
+			.map(|xt| match &xt.preamble {
+				Preamble::Signed(from, _, ((CheckNonce(nonce), ..), ..)) =>
+					(Some((*from, *nonce)), TransferData::try_from(xt).is_ok()),
+				_ => (None, false),
+			})
+			.collect::<Vec<_>>();
+		let alice = Sr25519Keyring::Alice.public();
+		let bob = Sr25519Keyring::Bob.public();
+		assert_eq!(
+			summary,
+			vec![
+				(Some((alice, 0)), true),
+				(Some((bob, 0)), true),
+				(None, false),
+				(Some((alice, 1)), true),
+				(None, false),
+				(Some((alice, 2)), false),
+			]
+		);
+		assert!(matches!(
+			workload[5].function,
+			RuntimeCall::SubstrateTest(substrate_test_pallet::Call::call_with_priority {
+				priority: 7
+			})
+		));
+		assert_ne!(workload[2], workload[4]);
+		workload.iter().for_each(|xt| {
+			extrinsic::assert_valid(xt);
+		});
+	}
+
+	#[test]
+	fn new_tagged_call_works() {
+		let (requires, provides) = (vec![vec![1, 2]], vec![vec![3], vec![4]]);
+
+		let (_, validity) = ExtrinsicBuilder::new_tagged_call(requires.clone(), provides.clone())
+			.build_and_validate();
+		assert_eq!(validity.requires, requires);
+		assert_eq!(validity.provides, provides);
+
+		let (_, validity) = ExtrinsicBuilder::new_tagged_call(requires.clone(), provides.clone())
+			.signer(Sr25519Keyring::Alice.pair())
+			.build_and_validate();
+		assert!(provides.iter().all(|tag| validity.provides.contains(tag)));
+		assert_eq!(validity.provides.len(), provides.len() + 1);
+	}
+
+	#[test]
+	fn build_and_validate_works() {
+		let (xt, validity) = ExtrinsicBuilder::new_call_do_not_propagate().build_and_validate();
+		assert!(!validity.propagate);
+		assert!(matches!(
+			xt.function,
+			RuntimeCall::SubstrateTest(substrate_test_pallet::Call::call_do_not_propagate {})
+		));
+
+		let (_, validity) = ExtrinsicBuilder::new_include_data(vec![1]).build_and_validate();
+		assert!(validity.propagate);
+	}
+
+	#[test]
+	fn new_call_do_not_propagate_signed_works() {
+		for signer in [Sr25519Keyring::Alice, Sr25519Keyring::Bob, Sr25519Keyring::Charlie] {
+			let (xt, validity) = ExtrinsicBuilder::new_call_do_not_propagate_signed(signer.pair())
+				.build_and_validate();
+			assert!(!validity.propagate);
+			let Preamble::Signed(address, _, _) = xt.preamble else {
+				panic!("extrinsic shall be signed")
+			};
+			assert_eq!(address, signer.public());
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "Extrinsic is not valid at genesis")]
+	fn assert_valid_panics_on_bad_signature() {
+		let xt = ExtrinsicBuilder::new_include_data(vec![1, 2, 3]).build();
+		let Preamble::Signed(_, signature, _) = xt.preamble else {
+			panic!("extrinsic shall be signed")
+		};
+		let forged = ExtrinsicBuilder::new_include_data(vec![1, 2, 3])
+			.raw_signature(Sr25519Keyring::Bob.public(), signature)
+			.build();
+		extrinsic::assert_valid(&forged);
+	}
+
 	mod genesis_builder_tests {
 		use super::*;
 		use crate::genesismap::GenesisStorageBuilder;
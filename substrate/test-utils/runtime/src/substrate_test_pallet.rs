@@ -189,6 +189,31 @@ pub mod pallet {
 		pub fn read_and_panic(_origin: OriginFor<T>, count: u32) -> DispatchResult {
 			Self::execute_read(count, true)
 		}
+
+		/// This call declares the weight exceeding the maximal block weight.
+		///
+		/// It can never be included in the block, the validation is expected to fail with
+		/// `InvalidTransaction::ExhaustsResources`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::BlockWeights::get().max_block.saturating_mul(2))]
+		pub fn overweight_call(origin: OriginFor<T>) -> DispatchResult {
+			ensure_signed(origin)?;
+			Ok(())
+		}
+
+		/// This call is validated as `ValidTransaction` with given `requires` and `provides` tags.
+		///
+		/// Allows to control the dependencies between transactions explicitly. Can be submitted
+		/// unsigned, otherwise the tags derived from the signer's nonce are also added.
+		#[pallet::call_index(13)]
+		#[pallet::weight(100)]
+		pub fn call_with_tags(
+			_origin: OriginFor<T>,
+			_requires: Vec<Vec<u8>>,
+			_provides: Vec<Vec<u8>>,
+		) -> DispatchResult {
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -234,6 +259,11 @@ pub mod pallet {
 					provides: vec![BlakeTwo256::hash_of(&call).encode()],
 					..Default::default()
 				}),
+				Call::call_with_tags { requires, provides } => Ok(ValidTransaction {
+					requires: requires.clone(),
+					provides: provides.clone(),
+					..Default::default()
+				}),
 				_ => Err(TransactionValidityError::Invalid(InvalidTransaction::Call)),
 			}
 		}
@@ -247,6 +277,11 @@ pub fn validate_runtime_call<T: pallet::Config>(call: &pallet::Call<T>) -> Trans
 			Ok(ValidTransaction { propagate: false, ..Default::default() }),
 		Call::call_with_priority { priority } =>
 			Ok(ValidTransaction { priority: *priority, ..Default::default() }),
+		Call::call_with_tags { requires, provides } => Ok(ValidTransaction {
+			requires: requires.clone(),
+			provides: provides.clone(),
+			..Default::default()
+		}),
 		_ => Ok(Default::default()),
 	}
 }
@@ -483,6 +483,16 @@ impl ChainApi for TestApi {
 		Self::hash_and_length_inner(ex)
 	}
 
+	fn best_block_hash(&self) -> Result<Option<<Self::Block as BlockT>::Hash>, Self::Error> {
+		Ok(self
+			.chain
+			.read()
+			.block_by_number
+			.values()
+			.rev()
+			.find_map(|blocks| blocks.iter().find(|b| b.1.is_best()).map(|b| b.0.header().hash())))
+	}
+
 	fn block_body(&self, hash: <Self::Block as BlockT>::Hash) -> Self::BodyFuture {
 		futures::future::ready(Ok(self
 			.chain